@@ -0,0 +1,1876 @@
+pub mod output;
+pub mod state;
+
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    sync::atomic::Ordering,
+    sync::mpsc::{Receiver, RecvTimeoutError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use output::{AudioOutput, NullOutput, OpenDeviceFn, open_device_output};
+use state::{BackgroundLoopEvent, BackgroundState, BackgroundStatusEvent};
+
+use crate::play_count::PlayCounts;
+use crate::resume_position::ResumePositions;
+use crate::session::{ResumePoint, Session};
+use crate::state::MusicList;
+
+/// 출력 장치가 없을 때 다시 열기를 시도하는 간격
+const OUTPUT_RETRY_INTERVAL: Duration = Duration::from_secs(3);
+
+/// 재생 위치 이벤트를 보내는 간격
+const POSITION_PUBLISH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// 세션을 주기적으로 저장하는 간격 (비정상 종료에 대비)
+const SESSION_SAVE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// 이보다 앞에서 멈춘 곡은 재생 위치를 기억하지 않는다.
+const RESUME_POSITION_MIN_PROGRESS: Duration = Duration::from_secs(5);
+
+/// 끝까지 이만큼 남기고 멈춘 곡은 다 들은 것으로 보고 재생 위치를 지운다.
+const RESUME_POSITION_END_MARGIN: Duration = Duration::from_secs(10);
+
+/// 이만큼(또는 곡 길이의 절반까지) 들어야 재생 횟수를 센다.
+const PLAY_COUNT_LISTENED: Duration = Duration::from_secs(240);
+
+/// 백그라운드 루프 시작 옵션
+pub struct BackgroundLoopOptions {
+    pub initial_volume: f32,
+    pub open_device: OpenDeviceFn,
+    pub resume_point: Option<ResumePoint>, // 지난 세션에서 이어서 재생할 지점
+    pub resume_position_min_duration: Duration, // 곡별 재생 위치를 기억할 최소 곡 길이
+    pub app_data_path: PathBuf,            // 세션, 재생 위치, 재생 횟수를 저장할 폴더
+}
+
+/// 백그라운드 재생 상태 머신
+/// 이벤트 처리와 주기적인 Tick을 분리해서 실제 장치 없이도 재생 순서를 검증할 수 있도록 한다.
+pub struct BackgroundLoop {
+    output: Box<dyn AudioOutput>,
+    open_device: OpenDeviceFn,
+    last_output_retry: Instant,
+    last_position_publish: Instant,
+
+    session_path: Option<PathBuf>, // None이면 세션을 저장하지 않는다. (테스트)
+    last_session_save: Instant,
+    last_saved_session: Option<Session>,
+
+    resume_positions: ResumePositions,
+    resume_positions_path: Option<PathBuf>, // None이면 곡별 재생 위치를 파일에 저장하지 않는다. (테스트)
+    resume_position_min_duration: Duration,
+
+    play_counts: PlayCounts,
+    play_counts_path: Option<PathBuf>, // None이면 재생 횟수를 파일에 저장하지 않는다. (테스트)
+    is_play_count_pending: bool,       // 현재 곡을 충분히 들으면 재생 횟수를 센다.
+    is_play_counts_dirty: bool,        // 세션과 함께 저장할 재생 횟수 변경이 있다.
+
+    background_state: BackgroundState,
+    music_list: MusicList,
+    random_indices: Vec<usize>, // shuffled index list
+}
+
+impl BackgroundLoop {
+    pub fn new(
+        output: Box<dyn AudioOutput>,
+        background_state: BackgroundState,
+        music_list: MusicList,
+        session_path: Option<PathBuf>,
+    ) -> Self {
+        let mut random_indices = (0..music_list.list.len()).collect::<Vec<_>>();
+
+        {
+            use rand::seq::SliceRandom;
+            random_indices.shuffle(&mut rand::rng());
+        }
+
+        background_state
+            .is_output_available
+            .store(output.is_available(), Ordering::Release);
+
+        Self {
+            output,
+            open_device: open_device_output,
+            last_output_retry: Instant::now(),
+            last_position_publish: Instant::now(),
+            session_path,
+            last_session_save: Instant::now(),
+            last_saved_session: None,
+            resume_positions: ResumePositions::default(),
+            resume_positions_path: None,
+            resume_position_min_duration: Duration::from_secs(
+                crate::config::Config::DEFAULT_RESUME_POSITION_MIN_SECS,
+            ),
+            play_counts: PlayCounts::default(),
+            play_counts_path: None,
+            is_play_count_pending: false,
+            is_play_counts_dirty: false,
+            background_state,
+            music_list,
+            random_indices,
+        }
+    }
+
+    /// 곡별 재생 위치 저장소를 연결한다.
+    pub fn set_resume_positions(
+        &mut self,
+        resume_positions: ResumePositions,
+        resume_positions_path: Option<PathBuf>,
+        resume_position_min_duration: Duration,
+    ) {
+        self.resume_positions = resume_positions;
+        self.resume_positions_path = resume_positions_path;
+        self.resume_position_min_duration = resume_position_min_duration;
+    }
+
+    /// 재생 횟수 저장소를 연결한다.
+    pub fn set_play_counts(&mut self, play_counts: PlayCounts, play_counts_path: Option<PathBuf>) {
+        self.play_counts = play_counts;
+        self.play_counts_path = play_counts_path;
+    }
+
+    /// 시작 시 첫 곡(또는 지난 세션에서 재생하던 곡) 자동 재생
+    pub fn start(&mut self, resume_point: Option<ResumePoint>) {
+        if self.music_list.list.is_empty() {
+            return;
+        }
+
+        let Some(resume_point) = resume_point
+            .filter(|resume_point| resume_point.music_index < self.music_list.list.len())
+        else {
+            self.play_current_or_skip(Step::Forward);
+            return;
+        };
+
+        if let Some(random_indices) = resume_point.random_indices {
+            self.random_indices = random_indices;
+        }
+
+        let current_index = if self.background_state.is_random_mode.load(Ordering::Acquire) {
+            self.random_indices
+                .iter()
+                .position(|&i| i == resume_point.music_index)
+                .unwrap_or_default()
+        } else {
+            resume_point.music_index
+        };
+
+        self.background_state
+            .current_index
+            .store(current_index, Ordering::Relaxed);
+
+        self.play_current_or_skip(Step::Forward);
+
+        // 재생 중이던 곡을 그대로 열었을 때만 위치와 일시정지 상태를 복원한다.
+        if self
+            .background_state
+            .current_music_index
+            .load(Ordering::Acquire)
+            != resume_point.music_index
+            || self.output.empty()
+        {
+            return;
+        }
+
+        if !resume_point.position.is_zero()
+            && let Err(error) = self.output.seek(resume_point.position)
+        {
+            log::error!("Failed to seek: {:?}", error);
+        }
+        self.skip_play_count_if_listened();
+
+        if resume_point.is_paused {
+            self.output.pause();
+            self.set_paused(true);
+        }
+    }
+
+    pub fn handle_event(&mut self, event: BackgroundLoopEvent) {
+        match event {
+            BackgroundLoopEvent::Pause => {
+                if !self.output.is_paused() {
+                    self.output.pause();
+                }
+                self.set_paused(true);
+            }
+            BackgroundLoopEvent::Resume => {
+                // 정지된 상태면 현재 곡을 처음부터 다시 재생
+                if self.output.empty() {
+                    self.play_current_or_skip(Step::Forward);
+                    return;
+                }
+
+                if self.output.is_paused() {
+                    self.output.play();
+                }
+                self.set_paused(false);
+            }
+            BackgroundLoopEvent::Stop => {
+                self.stop();
+            }
+            BackgroundLoopEvent::StopAfterCurrent(flag) => {
+                self.set_stop_after_current(flag);
+            }
+            BackgroundLoopEvent::Next => {
+                self.move_to_next_index();
+                self.play_current_or_skip(Step::Forward);
+            }
+            BackgroundLoopEvent::Previous => {
+                self.move_to_previous_index();
+                self.play_current_or_skip(Step::Backward);
+            }
+            BackgroundLoopEvent::DirectPlayMusic(index) => {
+                if index >= self.music_list.list.len() {
+                    // 범위를 벗어난 인덱스는 무시 (리스트 변경 등으로 stale된 클로저 방지)
+                    return;
+                }
+
+                // 재생 중인 곡을 다시 고르면 처음부터 재생한다.
+                let is_replay = !self.output.empty()
+                    && self
+                        .background_state
+                        .current_music_index
+                        .load(Ordering::Acquire)
+                        == index;
+
+                // current_index를 직접 재생한 곡의 위치로 맞춰
+                // 이후 Next/Previous 및 자동 다음 곡 연결이 올바르게 이어지도록 함
+                if self.background_state.is_random_mode.load(Ordering::Acquire) {
+                    // 랜덤 모드: random_indices에서 index가 위치한 슬롯을 역추적
+                    if let Some(slot) = self.random_indices.iter().position(|&i| i == index) {
+                        self.background_state
+                            .current_index
+                            .store(slot, Ordering::Relaxed);
+                    }
+                } else {
+                    self.background_state
+                        .current_index
+                        .store(index, Ordering::Relaxed);
+                }
+
+                self.play_current_or_skip(Step::Forward);
+
+                let music = &self.music_list.list[index];
+
+                // 곡을 떠나면서 기억한 위치는 지워서 다시 처음부터 듣도록 한다.
+                if is_replay {
+                    let file_path = music.file_path.clone();
+                    self.set_resume_position(file_path, None);
+                    return;
+                }
+
+                // 긴 곡은 마지막으로 듣던 위치부터 이어서 재생
+                if self
+                    .background_state
+                    .current_music_index
+                    .load(Ordering::Acquire)
+                    == index
+                    && !self.output.empty()
+                    && let Some(position) = self.resume_positions.get(&music.file_path)
+                    && let Err(error) = self.output.seek(position)
+                {
+                    log::error!("Failed to seek: {:?}", error);
+                }
+                self.skip_play_count_if_listened();
+            }
+            BackgroundLoopEvent::MusicListChanged(music_list) => {
+                self.replace_music_list(music_list);
+            }
+            BackgroundLoopEvent::SeekBy(offset_millis) => {
+                let position = self.output.position();
+                let offset = Duration::from_millis(offset_millis.unsigned_abs());
+
+                let position = if offset_millis < 0 {
+                    position.saturating_sub(offset)
+                } else {
+                    position + offset
+                };
+
+                self.seek(position);
+            }
+            BackgroundLoopEvent::ResumePositionMinDurationChanged(duration) => {
+                self.resume_position_min_duration = duration;
+            }
+            BackgroundLoopEvent::VolumeChanged(volume) => {
+                self.output.set_volume(volume);
+                self.background_state
+                    .publish(BackgroundStatusEvent::VolumeChanged(volume));
+            }
+            BackgroundLoopEvent::RandomModeChanged(is_random_mode) => {
+                self.set_random_mode(is_random_mode);
+            }
+            BackgroundLoopEvent::Shutdown(reply) => {
+                self.save_session();
+                let _ = reply.send(());
+            }
+            BackgroundLoopEvent::StartUp => {}
+        }
+    }
+
+    pub fn tick(&mut self) {
+        self.output_device_tick();
+        self.position_tick();
+        self.play_count_tick();
+        self.session_tick();
+
+        // 일시정지 중에는 자동 다음 곡 재생을 하지 않는다.
+        // (일시정지 상태에서 sink가 비었다고 해서 임의로 다음 곡을 재생하면
+        //  사용자가 일시정지했음에도 재생이 시작되는 버그가 발생함)
+        if self.output.empty() && !self.output.is_paused() {
+            // 끝까지 들은 곡은 기억해둔 재생 위치를 지우고 재생 횟수를 센다.
+            if self.is_play_count_pending {
+                self.count_play();
+            }
+            if self.music_list.is_not_empty() {
+                let index = self
+                    .background_state
+                    .current_music_index
+                    .load(Ordering::Acquire);
+                let file_path = self.music_list.list[index].file_path.clone();
+
+                self.set_resume_position(file_path, None);
+            }
+
+            self.move_to_next_index();
+
+            // 현재 곡까지만 재생하도록 설정했으면 다음 곡으로 넘어가지 않고 멈춘다. (한 번만 적용)
+            if self
+                .background_state
+                .stop_after_current
+                .load(Ordering::Acquire)
+            {
+                self.set_stop_after_current(false);
+                self.stop();
+                return;
+            }
+
+            self.play_current_or_skip(Step::Forward);
+        }
+    }
+
+    /// 재생을 멈추고 재생 위치를 처음으로 되돌린다.
+    /// 다시 재생하면 current_index가 가리키는 곡을 처음부터 재생한다.
+    fn stop(&mut self) {
+        self.remember_resume_position();
+
+        // clear는 출력을 일시정지 상태로 만들기 때문에 Tick에서 자동 다음 곡 재생이 일어나지 않는다.
+        self.output.clear();
+        self.set_paused(true);
+        self.set_stopped(true);
+    }
+
+    fn output_device_tick(&mut self) {
+        // 재생 중에 장치가 사라지면 Null 출력으로 전환한다.
+        if self.output.is_lost() {
+            log::warn!("Audio output device lost");
+
+            self.output = Box::new(NullOutput::following(self.output.as_ref()));
+            self.last_output_retry = Instant::now();
+
+            self.background_state
+                .is_output_available
+                .store(false, Ordering::Release);
+            self.background_state
+                .publish(BackgroundStatusEvent::OutputDeviceLost);
+        }
+
+        if self.output.is_available() || self.last_output_retry.elapsed() < OUTPUT_RETRY_INTERVAL {
+            return;
+        }
+
+        self.last_output_retry = Instant::now();
+
+        if let Ok(mut device_output) = (self.open_device)(self.output.volume()) {
+            log::info!("Audio output device opened");
+
+            // Null 출력에 걸려있던 곡을 실제 장치에서 처음부터 다시 재생
+            if !self.output.empty() {
+                if self.output.is_paused() {
+                    device_output.pause();
+                }
+
+                let index = self
+                    .background_state
+                    .current_music_index
+                    .load(Ordering::Acquire);
+
+                if let Err(error) = device_output.append(&self.music_list.list[index]) {
+                    log::error!("Failed to play music: {:?}", error);
+                }
+            }
+
+            self.output = device_output;
+
+            self.background_state
+                .is_output_available
+                .store(true, Ordering::Release);
+            self.background_state
+                .publish(BackgroundStatusEvent::OutputDeviceRestored);
+        }
+    }
+
+    fn position_tick(&mut self) {
+        if self.last_position_publish.elapsed() < POSITION_PUBLISH_INTERVAL {
+            return;
+        }
+
+        self.last_position_publish = Instant::now();
+
+        let (position, duration) = if self.output.empty() {
+            (Duration::ZERO, None)
+        } else {
+            (self.output.position(), self.output.total_duration())
+        };
+
+        let previous_position = self
+            .background_state
+            .position_millis
+            .swap(position.as_millis() as u64, Ordering::AcqRel);
+        self.background_state.duration_millis.store(
+            duration.unwrap_or_default().as_millis() as u64,
+            Ordering::Release,
+        );
+
+        // 일시정지 등으로 위치가 그대로면 이벤트를 보내지 않는다.
+        if previous_position != position.as_millis() as u64 {
+            self.background_state
+                .publish(BackgroundStatusEvent::PositionChanged { position, duration });
+        }
+    }
+
+    /// 재생 목록을 교체한다.
+    /// 재생 중인 곡과 랜덤 순서는 곡 단위로 유지하고, 재생 중인 곡이 목록에서 사라졌으면 재생을 멈춘다.
+    fn replace_music_list(&mut self, mut music_list: MusicList) {
+        // 큰 목록을 태그를 읽는 동안 여러 번 다시 정렬하므로 경로로 바로 찾는다.
+        let new_indices = music_list
+            .list
+            .iter()
+            .enumerate()
+            .map(|(index, music)| (music.file_path.clone(), index))
+            .collect::<HashMap<_, _>>();
+        let index_of = |file_path: &PathBuf| new_indices.get(file_path).copied();
+
+        let current_file_path = self
+            .music_list
+            .list
+            .get(
+                self.background_state
+                    .current_music_index
+                    .load(Ordering::Acquire),
+            )
+            .map(|music| music.file_path.clone());
+
+        // 기존 랜덤 순서를 새 인덱스로 옮기고, 새로 생긴 곡은 무작위 순서로 뒤에 붙인다.
+        let mut random_indices = self
+            .random_indices
+            .iter()
+            .filter_map(|&index| index_of(&self.music_list.list[index].file_path))
+            .collect::<Vec<_>>();
+        let kept_indices = random_indices.iter().copied().collect::<HashSet<_>>();
+        let mut added_indices = (0..music_list.list.len())
+            .filter(|index| !kept_indices.contains(index))
+            .collect::<Vec<_>>();
+
+        {
+            use rand::seq::SliceRandom;
+            added_indices.shuffle(&mut rand::rng());
+        }
+        random_indices.extend(added_indices);
+
+        // 재생할 수 없는 곡 표시는 백그라운드 루프가 알고 있는 상태를 따른다.
+        {
+            let broken_musics = self.background_state.broken_musics.lock().unwrap();
+
+            for music in &mut music_list.list {
+                music.error = broken_musics.get(&music.file_path).cloned();
+            }
+        }
+
+        let current_music_index = current_file_path.as_ref().and_then(index_of);
+
+        if current_music_index.is_none() {
+            self.stop();
+        }
+
+        self.music_list = music_list;
+        self.random_indices = random_indices;
+
+        let current_music_index = current_music_index.unwrap_or_default();
+        let current_index = if self.background_state.is_random_mode.load(Ordering::Acquire) {
+            self.random_indices
+                .iter()
+                .position(|&index| index == current_music_index)
+                .unwrap_or_default()
+        } else {
+            current_music_index
+        };
+
+        self.background_state
+            .current_index
+            .store(current_index, Ordering::Relaxed);
+        self.background_state
+            .current_music_index
+            .store(current_music_index, Ordering::Release);
+
+        // 목록을 비운 경우에도 알리도록 곡 변경과 따로 보낸다.
+        self.background_state
+            .publish(BackgroundStatusEvent::PlaylistChanged);
+
+        if self.music_list.is_not_empty() {
+            self.background_state
+                .publish(BackgroundStatusEvent::MusicChanged(current_music_index));
+        }
+    }
+
+    /// 현재 곡의 재생 위치를 옮기고 바뀐 위치를 바로 알린다. (곡 길이를 넘지 않도록 맞춤)
+    fn seek(&mut self, position: Duration) {
+        if self.output.empty() {
+            return;
+        }
+
+        let duration = self.output.total_duration();
+        let position = match duration {
+            Some(duration) => position.min(duration),
+            None => position,
+        };
+
+        if let Err(error) = self.output.seek(position) {
+            log::error!("Failed to seek: {:?}", error);
+            return;
+        }
+
+        self.background_state
+            .position_millis
+            .store(position.as_millis() as u64, Ordering::Release);
+        self.background_state
+            .publish(BackgroundStatusEvent::PositionChanged { position, duration });
+    }
+
+    fn play_count_tick(&mut self) {
+        if !self.is_play_count_pending || self.output.empty() {
+            return;
+        }
+
+        if self.output.position() >= self.play_count_threshold() {
+            self.count_play();
+        }
+    }
+
+    /// 곡을 센 것으로 볼 재생 위치
+    fn play_count_threshold(&self) -> Duration {
+        self.output
+            .total_duration()
+            .map_or(PLAY_COUNT_LISTENED, |duration| {
+                (duration / 2).min(PLAY_COUNT_LISTENED)
+            })
+    }
+
+    /// 이어서 재생한 위치가 이미 기준을 넘었으면 (지난번에 센 곡이므로) 다시 세지 않는다.
+    fn skip_play_count_if_listened(&mut self) {
+        if !self.output.empty() && self.output.position() >= self.play_count_threshold() {
+            self.is_play_count_pending = false;
+        }
+    }
+
+    /// 현재 곡의 재생 횟수를 늘린다. 파일에는 세션을 저장할 때 함께 쓴다.
+    fn count_play(&mut self) {
+        self.is_play_count_pending = false;
+
+        if self.music_list.list.is_empty() {
+            return;
+        }
+
+        let index = self
+            .background_state
+            .current_music_index
+            .load(Ordering::Acquire);
+        let music = &mut self.music_list.list[index];
+        let file_path = music.file_path.clone();
+        let play_count = self.play_counts.increment(file_path.clone());
+        music.play_count = play_count;
+        self.is_play_counts_dirty = true;
+
+        self.background_state
+            .publish(BackgroundStatusEvent::PlayCountChanged {
+                file_path,
+                play_count,
+            });
+    }
+
+    fn save_play_counts(&mut self) {
+        if !self.is_play_counts_dirty {
+            return;
+        }
+        self.is_play_counts_dirty = false;
+
+        if let Some(play_counts_path) = &self.play_counts_path
+            && let Err(error) = self.play_counts.save(play_counts_path)
+        {
+            log::error!("Failed to save play counts: {:?}", error);
+        }
+    }
+
+    fn session_tick(&mut self) {
+        if self.last_session_save.elapsed() < SESSION_SAVE_INTERVAL {
+            return;
+        }
+
+        self.save_session();
+    }
+
+    /// 현재 재생 상태를 세션으로 만든다.
+    pub fn session(&self) -> Session {
+        let path_of = |index: usize| self.music_list.list[index].file_path.clone();
+
+        let current_file_path = if self.output.empty() {
+            None
+        } else {
+            Some(path_of(
+                self.background_state
+                    .current_music_index
+                    .load(Ordering::Acquire),
+            ))
+        };
+
+        Session {
+            queue: (0..self.music_list.list.len()).map(path_of).collect(),
+            current_file_path,
+            position_millis: self.output.position().as_millis() as u64,
+            is_paused: self.background_state.is_paused.load(Ordering::Acquire),
+            shuffle_order: self.random_indices.iter().copied().map(path_of).collect(),
+        }
+    }
+
+    /// 세션이 바뀌었을 때만 파일에 저장한다.
+    fn save_session(&mut self) {
+        self.last_session_save = Instant::now();
+
+        // 비정상 종료에 대비해 긴 곡의 재생 위치와 재생 횟수도 함께 기록
+        self.remember_resume_position();
+        self.save_play_counts();
+
+        let Some(session_path) = &self.session_path else {
+            return;
+        };
+
+        let session = self.session();
+
+        if self.last_saved_session.as_ref() == Some(&session) {
+            return;
+        }
+
+        if let Err(error) = session.save(session_path) {
+            log::error!("Failed to save session: {:?}", error);
+            return;
+        }
+
+        self.last_saved_session = Some(session);
+    }
+
+    /// 현재 곡이 긴 곡이면 재생 위치를 기억한다.
+    fn remember_resume_position(&mut self) {
+        if self.output.empty() {
+            return;
+        }
+
+        let Some(duration) = self.output.total_duration() else {
+            return;
+        };
+
+        if duration < self.resume_position_min_duration {
+            return;
+        }
+
+        let index = self
+            .background_state
+            .current_music_index
+            .load(Ordering::Acquire);
+        let file_path = self.music_list.list[index].file_path.clone();
+        let position = self.output.position();
+
+        // 막 시작했거나 거의 다 들은 곡은 처음부터 재생하는 것이 자연스럽다.
+        let position = (position >= RESUME_POSITION_MIN_PROGRESS
+            && position + RESUME_POSITION_END_MARGIN < duration)
+            .then_some(position);
+
+        self.set_resume_position(file_path, position);
+    }
+
+    fn set_resume_position(&mut self, file_path: PathBuf, position: Option<Duration>) {
+        if !self.resume_positions.set(file_path.clone(), position) {
+            return;
+        }
+
+        if let Some(resume_positions_path) = &self.resume_positions_path
+            && let Err(error) = self.resume_positions.save(resume_positions_path)
+        {
+            log::error!("Failed to save resume positions: {:?}", error);
+        }
+
+        self.background_state
+            .publish(BackgroundStatusEvent::ResumePositionChanged {
+                file_path,
+                position,
+            });
+    }
+
+    fn set_stopped(&mut self, is_stopped: bool) {
+        let was_stopped = self
+            .background_state
+            .is_stopped
+            .swap(is_stopped, Ordering::AcqRel);
+
+        if was_stopped != is_stopped {
+            self.background_state
+                .publish(BackgroundStatusEvent::StopChanged(is_stopped));
+        }
+    }
+
+    fn set_stop_after_current(&mut self, stop_after_current: bool) {
+        let was_set = self
+            .background_state
+            .stop_after_current
+            .swap(stop_after_current, Ordering::AcqRel);
+
+        if was_set != stop_after_current {
+            self.background_state
+                .publish(BackgroundStatusEvent::StopAfterCurrentChanged(
+                    stop_after_current,
+                ));
+        }
+    }
+
+    fn set_paused(&mut self, is_paused: bool) {
+        let was_paused = self
+            .background_state
+            .is_paused
+            .swap(is_paused, Ordering::AcqRel);
+
+        if was_paused != is_paused {
+            self.background_state
+                .publish(BackgroundStatusEvent::PauseChanged(is_paused));
+        }
+    }
+
+    /// 랜덤 모드를 바꾼다. current_index는 모드마다 가리키는 순서가 다르므로
+    /// 재생 중인 곡을 계속 가리키도록 새 순서에 맞춰 옮긴다.
+    fn set_random_mode(&mut self, is_random_mode: bool) {
+        let was_random_mode = self
+            .background_state
+            .is_random_mode
+            .swap(is_random_mode, Ordering::AcqRel);
+
+        if was_random_mode == is_random_mode {
+            return;
+        }
+
+        let music_index = self
+            .background_state
+            .current_music_index
+            .load(Ordering::Acquire);
+
+        let current_index = if is_random_mode {
+            self.random_indices
+                .iter()
+                .position(|&i| i == music_index)
+                .unwrap_or_default()
+        } else {
+            music_index
+        };
+
+        self.background_state
+            .current_index
+            .store(current_index, Ordering::Relaxed);
+        self.background_state
+            .publish(BackgroundStatusEvent::RandomModeChanged(is_random_mode));
+    }
+
+    /// 순서상 다음 곡으로 current_index를 옮긴다. (끝에 도달하면 처음으로)
+    fn move_to_next_index(&mut self) {
+        let mut index = self.background_state.current_index.load(Ordering::Acquire);
+
+        index += 1;
+
+        if index >= self.music_list.list.len() {
+            index = 0;
+        }
+
+        self.background_state
+            .current_index
+            .store(index, Ordering::Relaxed);
+    }
+
+    /// 순서상 이전 곡으로 current_index를 옮긴다. (처음에 도달하면 끝으로)
+    fn move_to_previous_index(&mut self) {
+        if self.music_list.list.is_empty() {
+            return;
+        }
+
+        let mut index = self.background_state.current_index.load(Ordering::Acquire);
+
+        if index == 0 {
+            index = self.music_list.list.len() - 1;
+        } else {
+            index -= 1;
+        }
+
+        self.background_state
+            .current_index
+            .store(index, Ordering::Relaxed);
+    }
+
+    /// current_index부터 step 방향으로 재생 가능한 곡을 찾아 재생한다.
+    /// 손상된 파일 등으로 한 바퀴를 모두 실패하면 같은 곡을 계속 재시도하지 않도록 재생을 멈춘다.
+    fn play_current_or_skip(&mut self, step: Step) {
+        for _ in 0..self.music_list.list.len() {
+            if self.play_current_music().is_ok() {
+                return;
+            }
+
+            match step {
+                Step::Forward => self.move_to_next_index(),
+                Step::Backward => self.move_to_previous_index(),
+            }
+        }
+
+        if self.music_list.is_not_empty() {
+            log::warn!("No playable music in the list");
+        }
+
+        self.output.clear();
+        self.set_paused(true);
+        self.set_stopped(true);
+    }
+
+    /// current_index가 가리키는 곡을 재생한다. (랜덤 모드면 random_indices를 거친다)
+    fn play_current_music(&mut self) -> anyhow::Result<()> {
+        let mut index = self.background_state.current_index.load(Ordering::Acquire);
+
+        if self.background_state.is_random_mode.load(Ordering::Acquire) {
+            index = self.random_indices[index];
+        }
+
+        self.play_music(index)
+    }
+
+    /// 실제 곡 인덱스로 재생 대기열을 교체한다.
+    /// 재생할 수 없는 곡은 오류와 함께 손상된 곡으로 표시한다.
+    fn play_music(&mut self, index: usize) -> anyhow::Result<()> {
+        // 재생 중이던 곡을 떠나기 전에 재생 위치를 기억
+        self.remember_resume_position();
+
+        self.background_state
+            .current_music_index
+            .store(index, Ordering::Release);
+
+        self.output.clear();
+        self.output.play();
+
+        let music = &mut self.music_list.list[index];
+
+        if let Err(error) = self.output.append(music) {
+            log::error!("Failed to play music: {:?}", error);
+
+            let error_message = format!("{error:#}");
+            music.error = Some(error_message.clone());
+            self.background_state
+                .broken_musics
+                .lock()
+                .unwrap()
+                .insert(music.file_path.clone(), error_message.clone());
+            self.background_state
+                .publish(BackgroundStatusEvent::MusicErrorChanged {
+                    file_path: music.file_path.clone(),
+                    error: Some(error_message),
+                });
+
+            return Err(error);
+        }
+
+        if music.error.take().is_some() {
+            self.background_state
+                .broken_musics
+                .lock()
+                .unwrap()
+                .remove(&music.file_path);
+            self.background_state
+                .publish(BackgroundStatusEvent::MusicErrorChanged {
+                    file_path: music.file_path.clone(),
+                    error: None,
+                });
+        }
+
+        self.is_play_count_pending = true;
+
+        self.background_state
+            .publish(BackgroundStatusEvent::MusicChanged(index));
+        self.set_paused(false);
+        self.set_stopped(false);
+
+        Ok(())
+    }
+}
+
+/// 재생 가능한 곡을 찾을 때 건너뛰는 방향
+#[derive(Debug, Clone, Copy)]
+enum Step {
+    Forward,
+    Backward,
+}
+
+pub fn background_loop(
+    receiver: Receiver<BackgroundLoopEvent>,
+    background_state: BackgroundState,
+    music_list: MusicList,
+    options: BackgroundLoopOptions,
+) {
+    thread::spawn(move || {
+        // StartUp 이벤트가 들어올때까지 대기
+        loop {
+            if let Ok(BackgroundLoopEvent::StartUp) = receiver.recv() {
+                break;
+            }
+        }
+
+        // 출력 장치를 열 수 없으면 Null 출력으로 대체하고, 주기적으로 다시 열기를 시도한다.
+        let output = output::open_output_or_null(options.open_device, options.initial_volume);
+
+        let mut background_loop = BackgroundLoop::new(
+            output,
+            background_state,
+            music_list,
+            Some(crate::session::get_session_path(&options.app_data_path)),
+        );
+        background_loop.open_device = options.open_device;
+
+        let resume_positions_path =
+            crate::resume_position::get_resume_positions_path(&options.app_data_path);
+        background_loop.set_resume_positions(
+            crate::resume_position::read_resume_positions_if_exists(resume_positions_path.clone())
+                .unwrap_or_default(),
+            Some(resume_positions_path),
+            options.resume_position_min_duration,
+        );
+
+        let play_counts_path = crate::play_count::get_play_counts_path(&options.app_data_path);
+        background_loop.set_play_counts(
+            crate::play_count::read_play_counts_if_exists(play_counts_path.clone())
+                .unwrap_or_default(),
+            Some(play_counts_path),
+        );
+
+        background_loop.start(options.resume_point);
+
+        loop {
+            match receiver.recv_timeout(Duration::from_millis(100)) {
+                Ok(event) => {
+                    let is_shutdown = matches!(event, BackgroundLoopEvent::Shutdown(_));
+
+                    background_loop.handle_event(event);
+
+                    if is_shutdown {
+                        break;
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                // 프론트엔드가 사라졌으면 세션을 남기고 종료
+                Err(RecvTimeoutError::Disconnected) => {
+                    background_loop.save_session();
+                    break;
+                }
+            }
+
+            // Background Tick
+            background_loop.tick();
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::state::Music;
+    use output::fake::FakeOutput;
+
+    fn music_list(count: usize) -> MusicList {
+        MusicList {
+            list: (0..count)
+                .map(|i| Music {
+                    title: format!("{i}.mp3"),
+                    file_path: PathBuf::from(format!("/music/{i}.mp3")),
+                    ..Default::default()
+                })
+                .collect(),
+        }
+    }
+
+    fn path(index: usize) -> Option<PathBuf> {
+        Some(PathBuf::from(format!("/music/{index}.mp3")))
+    }
+
+    fn no_device(_: f32) -> anyhow::Result<Box<dyn AudioOutput>> {
+        Err(anyhow::anyhow!("no device"))
+    }
+
+    fn start_loop(count: usize, is_random_mode: bool) -> (BackgroundLoop, FakeOutput) {
+        start_loop_with_failing(count, is_random_mode, &[])
+    }
+
+    fn start_loop_with_failing(
+        count: usize,
+        is_random_mode: bool,
+        failing: &[usize],
+    ) -> (BackgroundLoop, FakeOutput) {
+        let output = FakeOutput::new();
+        output
+            .state()
+            .failing
+            .extend(failing.iter().filter_map(|&i| path(i)));
+
+        let mut background_loop = BackgroundLoop::new(
+            Box::new(output.clone()),
+            BackgroundState::new(is_random_mode),
+            music_list(count),
+            None,
+        );
+        background_loop.open_device = no_device;
+        background_loop.random_indices = (0..count).rev().collect();
+        background_loop.start(None);
+
+        (background_loop, output)
+    }
+
+    fn current_music_index(background_loop: &BackgroundLoop) -> usize {
+        background_loop
+            .background_state
+            .current_music_index
+            .load(Ordering::Acquire)
+    }
+
+    #[test]
+    fn plays_first_music_on_start() {
+        let (background_loop, output) = start_loop(3, false);
+
+        assert_eq!(path(0), output.current());
+        assert_eq!(0, current_music_index(&background_loop));
+    }
+
+    #[test]
+    fn does_nothing_on_start_with_empty_list() {
+        let (mut background_loop, output) = start_loop(0, false);
+
+        background_loop.handle_event(BackgroundLoopEvent::Next);
+        background_loop.handle_event(BackgroundLoopEvent::Previous);
+        background_loop.tick();
+
+        assert!(output.state().appended.is_empty());
+    }
+
+    #[test]
+    fn next_wraps_to_first_music() {
+        let (mut background_loop, output) = start_loop(3, false);
+
+        background_loop.handle_event(BackgroundLoopEvent::Next);
+        assert_eq!(path(1), output.current());
+
+        background_loop.handle_event(BackgroundLoopEvent::Next);
+        assert_eq!(path(2), output.current());
+
+        background_loop.handle_event(BackgroundLoopEvent::Next);
+        assert_eq!(path(0), output.current());
+        assert_eq!(1, output.state().queue.len());
+    }
+
+    #[test]
+    fn previous_wraps_to_last_music() {
+        let (mut background_loop, output) = start_loop(3, false);
+
+        background_loop.handle_event(BackgroundLoopEvent::Previous);
+        assert_eq!(path(2), output.current());
+
+        background_loop.handle_event(BackgroundLoopEvent::Previous);
+        assert_eq!(path(1), output.current());
+    }
+
+    #[test]
+    fn next_resumes_paused_playback() {
+        let (mut background_loop, output) = start_loop(3, false);
+
+        background_loop.handle_event(BackgroundLoopEvent::Pause);
+        assert!(output.state().is_paused);
+
+        background_loop.handle_event(BackgroundLoopEvent::Next);
+        assert!(!output.state().is_paused);
+        assert!(
+            !background_loop
+                .background_state
+                .is_paused
+                .load(Ordering::Acquire)
+        );
+    }
+
+    #[test]
+    fn random_mode_follows_random_indices() {
+        let (mut background_loop, output) = start_loop(3, true);
+
+        // random_indices = [2, 1, 0]
+        assert_eq!(path(2), output.current());
+        assert_eq!(2, current_music_index(&background_loop));
+
+        background_loop.handle_event(BackgroundLoopEvent::Next);
+        assert_eq!(path(1), output.current());
+
+        background_loop.handle_event(BackgroundLoopEvent::Previous);
+        background_loop.handle_event(BackgroundLoopEvent::Previous);
+        assert_eq!(path(0), output.current());
+    }
+
+    #[test]
+    fn direct_play_continues_from_played_music() {
+        let (mut background_loop, output) = start_loop(4, false);
+
+        background_loop.handle_event(BackgroundLoopEvent::DirectPlayMusic(2));
+        assert_eq!(path(2), output.current());
+
+        background_loop.handle_event(BackgroundLoopEvent::Next);
+        assert_eq!(path(3), output.current());
+    }
+
+    #[test]
+    fn direct_play_in_random_mode_continues_from_random_slot() {
+        let (mut background_loop, output) = start_loop(4, true);
+
+        // random_indices = [3, 2, 1, 0] 에서 1은 2번 슬롯
+        background_loop.handle_event(BackgroundLoopEvent::DirectPlayMusic(1));
+        assert_eq!(path(1), output.current());
+        assert_eq!(
+            2,
+            background_loop
+                .background_state
+                .current_index
+                .load(Ordering::Acquire)
+        );
+
+        background_loop.handle_event(BackgroundLoopEvent::Next);
+        assert_eq!(path(0), output.current());
+    }
+
+    #[test]
+    fn direct_play_ignores_out_of_range_index() {
+        let (mut background_loop, output) = start_loop(2, false);
+
+        background_loop.handle_event(BackgroundLoopEvent::DirectPlayMusic(5));
+
+        assert_eq!(path(0), output.current());
+        assert_eq!(1, output.state().appended.len());
+    }
+
+    #[test]
+    fn tick_advances_when_music_ends() {
+        let (mut background_loop, output) = start_loop(2, false);
+
+        background_loop.tick();
+        assert_eq!(path(0), output.current());
+
+        output.finish_current();
+        background_loop.tick();
+        assert_eq!(path(1), output.current());
+
+        output.finish_current();
+        background_loop.tick();
+        assert_eq!(path(0), output.current());
+    }
+
+    #[test]
+    fn tick_does_not_advance_while_paused() {
+        let (mut background_loop, output) = start_loop(2, false);
+
+        background_loop.handle_event(BackgroundLoopEvent::Pause);
+        output.finish_current();
+        background_loop.tick();
+
+        assert_eq!(None, output.current());
+        assert_eq!(1, output.state().appended.len());
+    }
+
+    #[test]
+    fn volume_changes_are_forwarded_to_output() {
+        let (mut background_loop, output) = start_loop(1, false);
+
+        background_loop.handle_event(BackgroundLoopEvent::VolumeChanged(0.25));
+
+        assert_eq!(0.25, output.state().volume);
+    }
+
+    #[test]
+    fn toggling_random_mode_keeps_current_music() {
+        let (mut background_loop, output) = start_loop(5, false);
+
+        background_loop.handle_event(BackgroundLoopEvent::DirectPlayMusic(3));
+
+        // 랜덤 순서는 4, 3, 2, 1, 0
+        background_loop.handle_event(BackgroundLoopEvent::RandomModeChanged(true));
+        background_loop.handle_event(BackgroundLoopEvent::Next);
+        assert_eq!(path(2), output.current());
+
+        background_loop.handle_event(BackgroundLoopEvent::RandomModeChanged(false));
+        background_loop.handle_event(BackgroundLoopEvent::Next);
+        assert_eq!(path(3), output.current());
+    }
+
+    #[test]
+    fn null_output_holds_current_music() {
+        let mut background_loop = BackgroundLoop::new(
+            Box::new(NullOutput::new(1.0)),
+            BackgroundState::new(false),
+            music_list(3),
+            None,
+        );
+        background_loop.open_device = no_device;
+        background_loop.start(None);
+
+        for _ in 0..5 {
+            background_loop.tick();
+        }
+
+        assert_eq!(0, current_music_index(&background_loop));
+        assert!(
+            !background_loop
+                .background_state
+                .is_output_available
+                .load(Ordering::Acquire)
+        );
+
+        background_loop.handle_event(BackgroundLoopEvent::Next);
+        assert_eq!(1, current_music_index(&background_loop));
+    }
+
+    #[test]
+    fn skips_broken_music_on_start() {
+        let (background_loop, output) = start_loop_with_failing(3, false, &[0]);
+
+        assert_eq!(path(1), output.current());
+        assert_eq!(1, current_music_index(&background_loop));
+
+        let music = &background_loop.music_list.list[0];
+        assert!(music.error.is_some());
+        assert!(
+            background_loop
+                .background_state
+                .broken_musics
+                .lock()
+                .unwrap()
+                .contains_key(&music.file_path)
+        );
+    }
+
+    #[test]
+    fn next_and_previous_skip_broken_music_in_their_direction() {
+        let (mut background_loop, output) = start_loop_with_failing(4, false, &[1, 3]);
+
+        background_loop.handle_event(BackgroundLoopEvent::Next);
+        assert_eq!(path(2), output.current());
+
+        background_loop.handle_event(BackgroundLoopEvent::Previous);
+        assert_eq!(path(0), output.current());
+
+        background_loop.handle_event(BackgroundLoopEvent::Previous);
+        assert_eq!(path(2), output.current());
+    }
+
+    #[test]
+    fn direct_play_of_broken_music_skips_to_next() {
+        let (mut background_loop, output) = start_loop_with_failing(3, false, &[1]);
+
+        background_loop.handle_event(BackgroundLoopEvent::DirectPlayMusic(1));
+
+        assert_eq!(path(2), output.current());
+    }
+
+    #[test]
+    fn tick_skips_broken_music_instead_of_retrying_it() {
+        let (mut background_loop, output) = start_loop_with_failing(3, false, &[1]);
+
+        output.finish_current();
+        background_loop.tick();
+        assert_eq!(path(2), output.current());
+
+        background_loop.tick();
+        assert_eq!(path(2), output.current());
+    }
+
+    #[test]
+    fn stops_after_a_full_lap_of_failures() {
+        let (mut background_loop, output) = start_loop_with_failing(3, false, &[0, 1, 2]);
+
+        assert_eq!(None, output.current());
+        assert!(output.state().is_paused);
+        assert!(
+            background_loop
+                .background_state
+                .is_paused
+                .load(Ordering::Acquire)
+        );
+
+        let attempts = output.state().attempts;
+        for _ in 0..10 {
+            background_loop.tick();
+        }
+        assert_eq!(attempts, output.state().attempts);
+    }
+
+    #[test]
+    fn recovered_music_is_no_longer_broken() {
+        let (mut background_loop, output) = start_loop_with_failing(2, false, &[1]);
+
+        background_loop.handle_event(BackgroundLoopEvent::DirectPlayMusic(1));
+        assert!(background_loop.music_list.list[1].error.is_some());
+
+        output.state().failing.clear();
+        background_loop.handle_event(BackgroundLoopEvent::DirectPlayMusic(1));
+
+        assert_eq!(path(1), output.current());
+        assert!(background_loop.music_list.list[1].error.is_none());
+        assert!(
+            background_loop
+                .background_state
+                .broken_musics
+                .lock()
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    fn received_events(
+        receiver: &mut tokio::sync::broadcast::Receiver<BackgroundStatusEvent>,
+    ) -> Vec<BackgroundStatusEvent> {
+        std::iter::from_fn(|| receiver.try_recv().ok()).collect()
+    }
+
+    #[test]
+    fn publishes_music_and_pause_changes() {
+        let (mut background_loop, _output) = start_loop(3, false);
+        let mut receiver = background_loop.background_state.subscribe();
+
+        background_loop.handle_event(BackgroundLoopEvent::Pause);
+        background_loop.handle_event(BackgroundLoopEvent::Pause);
+        background_loop.handle_event(BackgroundLoopEvent::Next);
+
+        assert_eq!(
+            vec![
+                BackgroundStatusEvent::PauseChanged(true),
+                BackgroundStatusEvent::MusicChanged(1),
+                BackgroundStatusEvent::PauseChanged(false),
+            ],
+            received_events(&mut receiver)
+        );
+    }
+
+    #[test]
+    fn publishes_music_errors() {
+        let (mut background_loop, _output) = start_loop_with_failing(3, false, &[1]);
+        let mut receiver = background_loop.background_state.subscribe();
+
+        background_loop.handle_event(BackgroundLoopEvent::Next);
+
+        let events = received_events(&mut receiver);
+        assert_eq!(
+            BackgroundStatusEvent::MusicErrorChanged {
+                file_path: PathBuf::from("/music/1.mp3"),
+                error: Some("Failed to decode \"/music/1.mp3\"".into()),
+            },
+            events[0]
+        );
+        assert_eq!(BackgroundStatusEvent::MusicChanged(2), events[1]);
+    }
+
+    #[test]
+    fn publishes_position_changes() {
+        let (mut background_loop, output) = start_loop(1, false);
+        let mut receiver = background_loop.background_state.subscribe();
+
+        output.state().position = Duration::from_secs(3);
+        output.state().total_duration = Some(Duration::from_secs(180));
+        background_loop.last_position_publish -= POSITION_PUBLISH_INTERVAL;
+        background_loop.tick();
+
+        assert_eq!(
+            vec![BackgroundStatusEvent::PositionChanged {
+                position: Duration::from_secs(3),
+                duration: Some(Duration::from_secs(180)),
+            }],
+            received_events(&mut receiver)
+        );
+        assert_eq!(
+            3000,
+            background_loop
+                .background_state
+                .position_millis
+                .load(Ordering::Acquire)
+        );
+    }
+
+    #[test]
+    fn snapshot_reflects_current_state() {
+        let (mut background_loop, _output) = start_loop_with_failing(3, false, &[2]);
+
+        background_loop.handle_event(BackgroundLoopEvent::DirectPlayMusic(2));
+        background_loop.handle_event(BackgroundLoopEvent::Pause);
+
+        let events = background_loop.background_state.snapshot_events();
+
+        assert!(events.contains(&BackgroundStatusEvent::MusicChanged(0)));
+        assert!(events.contains(&BackgroundStatusEvent::PauseChanged(true)));
+        assert!(events.contains(&BackgroundStatusEvent::MusicErrorChanged {
+            file_path: PathBuf::from("/music/2.mp3"),
+            error: Some("Failed to decode \"/music/2.mp3\"".into()),
+        }));
+    }
+
+    fn resume_loop(
+        count: usize,
+        is_random_mode: bool,
+        resume_point: ResumePoint,
+    ) -> (BackgroundLoop, FakeOutput) {
+        let output = FakeOutput::new();
+
+        let mut background_loop = BackgroundLoop::new(
+            Box::new(output.clone()),
+            BackgroundState::new(is_random_mode),
+            music_list(count),
+            None,
+        );
+        background_loop.open_device = no_device;
+        background_loop.start(Some(resume_point));
+
+        (background_loop, output)
+    }
+
+    #[test]
+    fn resumes_saved_music_and_position() {
+        let (mut background_loop, output) = resume_loop(
+            3,
+            false,
+            ResumePoint {
+                music_index: 2,
+                position: Duration::from_secs(30),
+                is_paused: true,
+                random_indices: None,
+            },
+        );
+
+        assert_eq!(path(2), output.current());
+        assert_eq!(Duration::from_secs(30), output.state().position);
+        assert!(output.state().is_paused);
+
+        // 일시정지 상태로 시작했으면 Tick에서 재생되지 않는다.
+        background_loop.tick();
+        assert_eq!(1, output.state().appended.len());
+
+        background_loop.handle_event(BackgroundLoopEvent::Next);
+        assert_eq!(path(0), output.current());
+    }
+
+    #[test]
+    fn resumes_saved_shuffle_order() {
+        let (mut background_loop, output) = resume_loop(
+            3,
+            true,
+            ResumePoint {
+                music_index: 0,
+                position: Duration::ZERO,
+                is_paused: false,
+                random_indices: Some(vec![1, 0, 2]),
+            },
+        );
+
+        assert_eq!(path(0), output.current());
+
+        background_loop.handle_event(BackgroundLoopEvent::Next);
+        assert_eq!(path(2), output.current());
+    }
+
+    #[test]
+    fn session_captures_current_state() {
+        let (mut background_loop, output) = start_loop(3, true);
+
+        background_loop.handle_event(BackgroundLoopEvent::Next);
+        background_loop.handle_event(BackgroundLoopEvent::Pause);
+        output.state().position = Duration::from_millis(1500);
+
+        let session = background_loop.session();
+
+        assert_eq!(
+            Session {
+                queue: vec![path(0), path(1), path(2)]
+                    .into_iter()
+                    .flatten()
+                    .collect(),
+                current_file_path: path(1),
+                position_millis: 1500,
+                is_paused: true,
+                shuffle_order: vec![path(2), path(1), path(0)]
+                    .into_iter()
+                    .flatten()
+                    .collect(),
+            },
+            session
+        );
+    }
+
+    #[test]
+    fn shutdown_replies_after_saving() {
+        let (mut background_loop, _output) = start_loop(1, false);
+        let (reply_sender, reply_receiver) = std::sync::mpsc::channel();
+
+        background_loop.handle_event(BackgroundLoopEvent::Shutdown(reply_sender));
+
+        assert!(reply_receiver.try_recv().is_ok());
+    }
+
+    fn long_music_loop(count: usize) -> (BackgroundLoop, FakeOutput) {
+        let (mut background_loop, output) = start_loop(count, false);
+
+        background_loop.resume_position_min_duration = Duration::from_secs(600);
+        output.state().total_duration = Some(Duration::from_secs(3600));
+
+        (background_loop, output)
+    }
+
+    #[test]
+    fn remembers_position_of_long_music_when_switching_away() {
+        let (mut background_loop, output) = long_music_loop(2);
+        let mut receiver = background_loop.background_state.subscribe();
+
+        output.state().position = Duration::from_secs(125);
+        background_loop.handle_event(BackgroundLoopEvent::Next);
+
+        assert_eq!(
+            Some(Duration::from_secs(125)),
+            background_loop
+                .resume_positions
+                .get(&PathBuf::from("/music/0.mp3"))
+        );
+        assert!(received_events(&mut receiver).contains(
+            &BackgroundStatusEvent::ResumePositionChanged {
+                file_path: PathBuf::from("/music/0.mp3"),
+                position: Some(Duration::from_secs(125)),
+            }
+        ));
+
+        background_loop.handle_event(BackgroundLoopEvent::DirectPlayMusic(0));
+
+        assert_eq!(path(0), output.current());
+        assert_eq!(Duration::from_secs(125), output.state().position);
+    }
+
+    #[test]
+    fn replaying_current_music_restarts_from_beginning() {
+        let (mut background_loop, output) = long_music_loop(2);
+
+        output.state().position = Duration::from_secs(125);
+        background_loop.handle_event(BackgroundLoopEvent::DirectPlayMusic(0));
+
+        assert_eq!(path(0), output.current());
+        assert_eq!(Duration::ZERO, output.state().position);
+        assert_eq!(
+            None,
+            background_loop
+                .resume_positions
+                .get(&PathBuf::from("/music/0.mp3"))
+        );
+    }
+
+    #[test]
+    fn does_not_remember_position_of_short_music() {
+        let (mut background_loop, output) = long_music_loop(2);
+
+        output.state().total_duration = Some(Duration::from_secs(180));
+        output.state().position = Duration::from_secs(125);
+        background_loop.handle_event(BackgroundLoopEvent::Next);
+
+        assert!(background_loop.resume_positions.positions.is_empty());
+    }
+
+    #[test]
+    fn forgets_position_of_finished_music() {
+        let (mut background_loop, output) = long_music_loop(2);
+
+        background_loop
+            .resume_positions
+            .set(PathBuf::from("/music/0.mp3"), Some(Duration::from_secs(60)));
+
+        output.finish_current();
+        background_loop.tick();
+
+        assert!(background_loop.resume_positions.positions.is_empty());
+    }
+
+    #[test]
+    fn forgets_position_near_the_end() {
+        let (mut background_loop, output) = long_music_loop(2);
+
+        background_loop
+            .resume_positions
+            .set(PathBuf::from("/music/0.mp3"), Some(Duration::from_secs(60)));
+
+        output.state().position = Duration::from_secs(3595);
+        background_loop.handle_event(BackgroundLoopEvent::Next);
+
+        assert!(background_loop.resume_positions.positions.is_empty());
+    }
+
+    #[test]
+    fn stop_clears_output_and_resume_restarts_current_music() {
+        let (mut background_loop, output) = start_loop(3, false);
+
+        background_loop.handle_event(BackgroundLoopEvent::Next);
+        output.state().position = Duration::from_secs(42);
+        background_loop.handle_event(BackgroundLoopEvent::Stop);
+
+        assert_eq!(None, output.current());
+        assert_eq!(Duration::ZERO, output.state().position);
+        assert!(
+            background_loop
+                .background_state
+                .is_paused
+                .load(Ordering::Acquire)
+        );
+        assert!(
+            background_loop
+                .background_state
+                .is_stopped
+                .load(Ordering::Acquire)
+        );
+
+        // 정지 중에는 Tick에서 다음 곡으로 넘어가지 않는다.
+        background_loop.tick();
+        assert_eq!(None, output.current());
+
+        background_loop.handle_event(BackgroundLoopEvent::Resume);
+        assert_eq!(path(1), output.current());
+        assert!(
+            !background_loop
+                .background_state
+                .is_stopped
+                .load(Ordering::Acquire)
+        );
+    }
+
+    #[test]
+    fn stop_after_current_stops_once_current_music_ends() {
+        let (mut background_loop, output) = start_loop(3, false);
+        let mut receiver = background_loop.background_state.subscribe();
+
+        background_loop.handle_event(BackgroundLoopEvent::StopAfterCurrent(true));
+        output.finish_current();
+        background_loop.tick();
+
+        assert_eq!(None, output.current());
+        assert!(
+            background_loop
+                .background_state
+                .is_stopped
+                .load(Ordering::Acquire)
+        );
+        assert!(
+            !background_loop
+                .background_state
+                .stop_after_current
+                .load(Ordering::Acquire)
+        );
+        assert!(
+            received_events(&mut receiver)
+                .contains(&BackgroundStatusEvent::StopAfterCurrentChanged(false))
+        );
+
+        // 다시 재생하면 다음 곡부터 이어서 재생
+        background_loop.handle_event(BackgroundLoopEvent::Resume);
+        assert_eq!(path(1), output.current());
+
+        output.finish_current();
+        background_loop.tick();
+        assert_eq!(path(2), output.current());
+    }
+
+    #[test]
+    fn seeks_within_current_music() {
+        let (mut background_loop, output) = start_loop(2, false);
+        let mut receiver = background_loop.background_state.subscribe();
+        output.state().total_duration = Some(Duration::from_secs(60));
+        output.state().position = Duration::from_secs(3);
+
+        background_loop.handle_event(BackgroundLoopEvent::SeekBy(-5000));
+        assert_eq!(Duration::ZERO, output.state().position);
+
+        background_loop.handle_event(BackgroundLoopEvent::SeekBy(58_000));
+        background_loop.handle_event(BackgroundLoopEvent::SeekBy(5000));
+        assert_eq!(Duration::from_secs(60), output.state().position);
+
+        assert_eq!(
+            Some(&BackgroundStatusEvent::PositionChanged {
+                position: Duration::from_secs(60),
+                duration: Some(Duration::from_secs(60)),
+            }),
+            received_events(&mut receiver).last()
+        );
+        assert_eq!(path(0), output.current());
+    }
+
+    fn reversed(music_list: &MusicList) -> MusicList {
+        MusicList {
+            list: music_list.list.iter().rev().cloned().collect(),
+        }
+    }
+
+    #[test]
+    fn reordered_music_list_keeps_current_music() {
+        let (mut background_loop, output) = start_loop(3, false);
+        let mut receiver = background_loop.background_state.subscribe();
+
+        background_loop.handle_event(BackgroundLoopEvent::Next);
+        let music_list = reversed(&background_loop.music_list);
+        background_loop.handle_event(BackgroundLoopEvent::MusicListChanged(music_list));
+
+        // 재생 중인 1번 곡은 끊기지 않고 새 목록의 1번 자리에 있다.
+        assert_eq!(path(1), output.current());
+        assert_eq!(1, current_music_index(&background_loop));
+        assert!(received_events(&mut receiver).contains(&BackgroundStatusEvent::MusicChanged(1)));
+
+        // 다음 곡은 새 순서를 따른다.
+        background_loop.handle_event(BackgroundLoopEvent::Next);
+        assert_eq!(path(0), output.current());
+    }
+
+    #[test]
+    fn reordered_music_list_keeps_random_order() {
+        let (mut background_loop, output) = start_loop(3, true);
+
+        // random_indices = [2, 1, 0] 이므로 2번 곡부터 재생
+        assert_eq!(path(2), output.current());
+
+        let music_list = reversed(&background_loop.music_list);
+        background_loop.handle_event(BackgroundLoopEvent::MusicListChanged(music_list));
+
+        background_loop.handle_event(BackgroundLoopEvent::Next);
+        assert_eq!(path(1), output.current());
+        background_loop.handle_event(BackgroundLoopEvent::Next);
+        assert_eq!(path(0), output.current());
+    }
+
+    #[test]
+    fn stops_when_current_music_is_removed_from_list() {
+        let (mut background_loop, output) = start_loop(3, false);
+        let mut receiver = background_loop.background_state.subscribe();
+
+        let music_list = MusicList {
+            list: background_loop.music_list.list[1..].to_vec(),
+        };
+        background_loop.handle_event(BackgroundLoopEvent::MusicListChanged(music_list));
+        assert!(received_events(&mut receiver).contains(&BackgroundStatusEvent::PlaylistChanged));
+
+        assert_eq!(None, output.current());
+        assert!(
+            background_loop
+                .background_state
+                .is_stopped
+                .load(Ordering::Acquire)
+        );
+
+        // 다시 재생하면 새 목록의 첫 곡부터
+        background_loop.handle_event(BackgroundLoopEvent::Resume);
+        assert_eq!(path(1), output.current());
+    }
+
+    #[test]
+    fn counts_plays_after_listening() {
+        let (mut background_loop, output) = start_loop(2, false);
+        let mut receiver = background_loop.background_state.subscribe();
+        output.state().total_duration = Some(Duration::from_secs(200));
+
+        output.state().position = Duration::from_secs(99);
+        background_loop.tick();
+        assert_eq!(
+            0,
+            background_loop
+                .play_counts
+                .get(&PathBuf::from("/music/0.mp3"))
+        );
+
+        output.state().position = Duration::from_secs(100);
+        background_loop.tick();
+        background_loop.tick();
+
+        assert_eq!(
+            1,
+            background_loop
+                .play_counts
+                .get(&PathBuf::from("/music/0.mp3"))
+        );
+        assert_eq!(
+            1,
+            received_events(&mut receiver)
+                .iter()
+                .filter(|event| matches!(event, BackgroundStatusEvent::PlayCountChanged { .. }))
+                .count()
+        );
+    }
+
+    #[test]
+    fn counts_music_played_to_the_end() {
+        let (mut background_loop, output) = start_loop(2, false);
+
+        output.finish_current();
+        background_loop.tick();
+
+        assert_eq!(
+            1,
+            background_loop
+                .play_counts
+                .get(&PathBuf::from("/music/0.mp3"))
+        );
+        assert_eq!(
+            0,
+            background_loop
+                .play_counts
+                .get(&PathBuf::from("/music/1.mp3"))
+        );
+    }
+
+    #[test]
+    fn does_not_count_restored_music_again() {
+        let (mut background_loop, output) = resume_loop(
+            2,
+            false,
+            ResumePoint {
+                music_index: 1,
+                position: Duration::from_secs(300),
+                is_paused: false,
+                random_indices: None,
+            },
+        );
+
+        background_loop.handle_event(BackgroundLoopEvent::Stop);
+        background_loop.handle_event(BackgroundLoopEvent::Resume);
+        background_loop.tick();
+        assert_eq!(
+            0,
+            background_loop
+                .play_counts
+                .get(&PathBuf::from("/music/1.mp3"))
+        );
+
+        // 정지 후 다시 재생하면 처음부터 다시 듣는 것이므로 충분히 들으면 센다.
+        output.state().position = Duration::from_secs(300);
+        background_loop.tick();
+        assert_eq!(
+            1,
+            background_loop
+                .play_counts
+                .get(&PathBuf::from("/music/1.mp3"))
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
+
+use rodio::{DeviceSinkBuilder, DeviceSinkError, MixerDeviceSink, Player, Source};
+
+use super::AudioOutput;
+use crate::state::Music;
+
+/// rodio 기본 출력 장치
+pub struct DeviceOutput {
+    _stream: MixerDeviceSink,
+    player: Player,
+    is_lost: Arc<AtomicBool>,
+    total_duration: Option<Duration>, // 마지막으로 추가한 곡의 길이
+}
+
+impl DeviceOutput {
+    pub fn open_default(volume: f32) -> Result<Self, DeviceSinkError> {
+        let is_lost = Arc::new(AtomicBool::new(false));
+
+        let stream = {
+            let is_lost = is_lost.clone();
+
+            DeviceSinkBuilder::from_default_device()
+                .and_then(|builder| {
+                    builder
+                        .with_error_callback(move |error| {
+                            use rodio::cpal::StreamError;
+
+                            if let StreamError::DeviceNotAvailable
+                            | StreamError::StreamInvalidated = error
+                            {
+                                log::error!("audio stream error: {:?}", error);
+                                is_lost.store(true, Ordering::Release);
+                            }
+                        })
+                        .open_stream()
+                })
+                .or_else(|_| DeviceSinkBuilder::open_default_sink())?
+        };
+
+        let player = Player::connect_new(stream.mixer());
+        player.set_volume(volume);
+
+        Ok(Self {
+            _stream: stream,
+            player,
+            is_lost,
+            total_duration: None,
+        })
+    }
+}
+
+impl AudioOutput for DeviceOutput {
+    fn append(&mut self, music: &Music) -> anyhow::Result<()> {
+        let file = std::fs::File::open(&music.file_path)?;
+
+        let source = rodio::Decoder::try_from(file)?;
+        self.total_duration = source.total_duration();
+        self.player.append(source);
+
+        Ok(())
+    }
+
+    fn play(&mut self) {
+        self.player.play();
+    }
+
+    fn pause(&mut self) {
+        self.player.pause();
+    }
+
+    fn is_paused(&self) -> bool {
+        self.player.is_paused()
+    }
+
+    fn clear(&mut self) {
+        self.player.clear();
+    }
+
+    fn empty(&self) -> bool {
+        self.player.empty()
+    }
+
+    fn volume(&self) -> f32 {
+        self.player.volume()
+    }
+
+    fn set_volume(&mut self, volume: f32) {
+        self.player.set_volume(volume);
+    }
+
+    fn position(&self) -> Duration {
+        self.player.get_pos()
+    }
+
+    fn seek(&mut self, position: Duration) -> anyhow::Result<()> {
+        self.player.try_seek(position)?;
+
+        Ok(())
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.total_duration
+    }
+
+    fn is_lost(&self) -> bool {
+        self.is_lost.load(Ordering::Acquire)
+    }
+}
@@ -0,0 +1,112 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    path::PathBuf,
+    sync::{Arc, Mutex, MutexGuard},
+    time::Duration,
+};
+
+use super::AudioOutput;
+use crate::state::Music;
+
+/// 테스트용 메모리 출력
+/// 추가된 곡을 기록하고, 곡이 끝나는 상황을 직접 흉내낼 수 있다.
+/// clone한 핸들끼리 상태를 공유하므로 루프에 넘긴 뒤에도 상태를 검사할 수 있다.
+#[derive(Debug, Clone, Default)]
+pub struct FakeOutput {
+    inner: Arc<Mutex<FakeOutputState>>,
+}
+
+#[derive(Debug, Default)]
+pub struct FakeOutputState {
+    pub appended: Vec<PathBuf>,   // 지금까지 추가된 모든 곡 (재생 이력)
+    pub queue: VecDeque<PathBuf>, // 현재 재생 대기열
+    pub is_paused: bool,
+    pub volume: f32,
+    pub failing: HashSet<PathBuf>, // 디코딩에 실패한 것처럼 처리할 곡
+    pub attempts: usize,           // 추가를 시도한 횟수 (실패 포함)
+    pub position: Duration,
+    pub total_duration: Option<Duration>,
+}
+
+impl FakeOutput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn state(&self) -> MutexGuard<'_, FakeOutputState> {
+        self.inner.lock().unwrap()
+    }
+
+    /// 현재 곡이 끝까지 재생된 것처럼 대기열에서 제거한다.
+    pub fn finish_current(&self) {
+        self.state().queue.pop_front();
+    }
+
+    /// 현재 재생 중인 곡
+    pub fn current(&self) -> Option<PathBuf> {
+        self.state().queue.front().cloned()
+    }
+}
+
+impl AudioOutput for FakeOutput {
+    fn append(&mut self, music: &Music) -> anyhow::Result<()> {
+        let mut state = self.state();
+
+        state.attempts += 1;
+
+        if state.failing.contains(&music.file_path) {
+            return Err(anyhow::anyhow!("Failed to decode {:?}", music.file_path));
+        }
+
+        state.appended.push(music.file_path.clone());
+        state.queue.push_back(music.file_path.clone());
+
+        Ok(())
+    }
+
+    fn play(&mut self) {
+        self.state().is_paused = false;
+    }
+
+    fn pause(&mut self) {
+        self.state().is_paused = true;
+    }
+
+    fn is_paused(&self) -> bool {
+        self.state().is_paused
+    }
+
+    fn clear(&mut self) {
+        let mut state = self.state();
+
+        state.queue.clear();
+        state.is_paused = true;
+        state.position = Duration::ZERO;
+    }
+
+    fn empty(&self) -> bool {
+        self.state().queue.is_empty()
+    }
+
+    fn volume(&self) -> f32 {
+        self.state().volume
+    }
+
+    fn set_volume(&mut self, volume: f32) {
+        self.state().volume = volume;
+    }
+
+    fn position(&self) -> Duration {
+        self.state().position
+    }
+
+    fn seek(&mut self, position: Duration) -> anyhow::Result<()> {
+        self.state().position = position;
+
+        Ok(())
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.state().total_duration
+    }
+}
@@ -0,0 +1,192 @@
+mod device;
+#[cfg(any(test, feature = "test-util"))]
+pub mod fake;
+
+pub use device::DeviceOutput;
+
+use std::time::Duration;
+
+use crate::state::Music;
+
+/// 백그라운드 루프가 구동하는 오디오 출력
+/// 실제 장치(rodio), 장치가 없을 때의 Null 출력, 테스트용 Fake 출력이 이를 구현한다.
+pub trait AudioOutput {
+    /// 곡을 디코딩해서 재생 대기열 끝에 추가한다.
+    fn append(&mut self, music: &Music) -> anyhow::Result<()>;
+
+    fn play(&mut self);
+
+    fn pause(&mut self);
+
+    fn is_paused(&self) -> bool;
+
+    /// 재생 대기열을 비운다. (rodio와 동일하게 일시정지 상태가 된다)
+    fn clear(&mut self);
+
+    /// 재생할 곡이 남아있지 않은지 여부
+    fn empty(&self) -> bool;
+
+    fn volume(&self) -> f32;
+
+    fn set_volume(&mut self, volume: f32);
+
+    /// 현재 곡의 재생 위치
+    fn position(&self) -> Duration;
+
+    /// 현재 곡의 재생 위치를 옮긴다.
+    fn seek(&mut self, position: Duration) -> anyhow::Result<()>;
+
+    /// 현재 곡의 전체 길이 (알 수 없으면 None)
+    fn total_duration(&self) -> Option<Duration>;
+
+    /// 실제로 소리를 내는 출력인지 여부 (false면 장치 다시 열기를 시도한다)
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    /// 재생 중에 장치가 사라졌는지 여부 (USB 장치 분리 등)
+    fn is_lost(&self) -> bool {
+        false
+    }
+}
+
+/// 오디오 장치를 열 수 없는 환경(장치 없음, headless 등)에서 사용하는 출력
+/// 백그라운드 스레드가 죽지 않고 나머지 기능은 그대로 동작하도록 한다.
+pub struct NullOutput {
+    has_source: bool,
+    is_paused: bool,
+    volume: f32,
+}
+
+impl NullOutput {
+    pub fn new(volume: f32) -> Self {
+        Self {
+            has_source: false,
+            is_paused: false,
+            volume,
+        }
+    }
+
+    /// 기존 출력의 재생 상태(일시정지, 볼륨, 곡 유무)를 이어받는다.
+    pub fn following(output: &dyn AudioOutput) -> Self {
+        Self {
+            has_source: !output.empty(),
+            is_paused: output.is_paused(),
+            volume: output.volume(),
+        }
+    }
+}
+
+impl AudioOutput for NullOutput {
+    fn append(&mut self, _music: &Music) -> anyhow::Result<()> {
+        // Null 출력은 소리를 내지 않으므로 곡이 끝나지 않은 것으로 간주한다.
+        // (곡이 끝났다고 판단하면 100ms마다 다음 곡으로 넘어가버림)
+        self.has_source = true;
+
+        Ok(())
+    }
+
+    fn play(&mut self) {
+        self.is_paused = false;
+    }
+
+    fn pause(&mut self) {
+        self.is_paused = true;
+    }
+
+    fn is_paused(&self) -> bool {
+        self.is_paused
+    }
+
+    fn clear(&mut self) {
+        self.has_source = false;
+        self.is_paused = true;
+    }
+
+    fn empty(&self) -> bool {
+        !self.has_source
+    }
+
+    fn volume(&self) -> f32 {
+        self.volume
+    }
+
+    fn set_volume(&mut self, volume: f32) {
+        self.volume = volume;
+    }
+
+    fn position(&self) -> Duration {
+        Duration::ZERO
+    }
+
+    fn seek(&mut self, _position: Duration) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+
+    fn is_available(&self) -> bool {
+        false
+    }
+}
+
+/// 출력 장치를 여는 함수 (테스트나 다른 출력을 쓰는 곳에서는 장치를 열지 않도록 교체한다)
+pub type OpenDeviceFn = fn(f32) -> anyhow::Result<Box<dyn AudioOutput>>;
+
+/// 기본 출력 장치를 연다.
+pub fn open_device_output(volume: f32) -> anyhow::Result<Box<dyn AudioOutput>> {
+    Ok(Box::new(DeviceOutput::open_default(volume)?))
+}
+
+/// 출력 장치를 열고, 실패하면 Null 출력을 반환한다.
+pub fn open_output_or_null(open_device: OpenDeviceFn, volume: f32) -> Box<dyn AudioOutput> {
+    match open_device(volume) {
+        Ok(output) => output,
+        Err(error) => {
+            log::error!("Failed to open default audio output device: {:?}", error);
+            Box::new(NullOutput::new(volume))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fake::FakeOutput;
+
+    fn no_device(_: f32) -> anyhow::Result<Box<dyn AudioOutput>> {
+        Err(anyhow::anyhow!("no device"))
+    }
+
+    #[test]
+    fn falls_back_to_null_output_without_device() {
+        let output = open_output_or_null(no_device, 0.4);
+
+        assert!(!output.is_available());
+        assert_eq!(0.4, output.volume());
+        assert!(output.empty());
+    }
+
+    #[test]
+    fn null_output_follows_previous_output() {
+        let mut device_output = FakeOutput::new();
+        device_output.set_volume(0.7);
+        device_output.pause();
+
+        let mut null_output = NullOutput::following(&device_output);
+
+        assert!(null_output.is_paused());
+        assert_eq!(0.7, null_output.volume());
+
+        // 곡을 추가하면 끝나지 않은 곡으로 간주하고, 비우면 일시정지 상태가 된다.
+        null_output.play();
+        null_output.append(&Music::default()).unwrap();
+        assert!(!null_output.empty());
+
+        null_output.clear();
+        assert!(null_output.empty());
+        assert!(null_output.is_paused());
+    }
+}
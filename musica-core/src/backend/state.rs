@@ -0,0 +1,347 @@
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, AtomicU64, AtomicUsize},
+        mpsc::Sender,
+    },
+    time::Duration,
+};
+
+use tokio::sync::broadcast::{self, error::RecvError};
+
+use crate::metadata::MusicMetadata;
+use crate::state::MusicList;
+
+pub enum BackgroundLoopEvent {
+    StartUp,
+    Pause,
+    Resume,
+    Stop,                   // 재생을 멈추고 재생 위치를 처음으로 되돌린다.
+    StopAfterCurrent(bool), // 현재 곡이 끝나면 다음 곡으로 넘어가지 않고 멈춘다.
+    Next,
+    Previous,
+    DirectPlayMusic(usize),
+    MusicListChanged(MusicList), // 정렬이나 폴더 변경으로 재생 목록이 바뀜 (재생 중인 곡은 유지)
+    SeekBy(i64),                 // 현재 위치에서 앞(양수)/뒤(음수)로 이동 (밀리초)
+    VolumeChanged(f32),
+    RandomModeChanged(bool),
+    ResumePositionMinDurationChanged(Duration), // 곡별 재생 위치를 기억할 최소 곡 길이
+    Shutdown(Sender<()>),                       // 세션을 저장한 뒤 루프를 종료하고 응답한다.
+}
+
+/// 백그라운드 루프가 재생 상태 변화를 알리는 이벤트
+#[derive(Debug, Clone, PartialEq)]
+pub enum BackgroundStatusEvent {
+    MusicChanged(usize), // 새로 재생을 시작한 곡의 실제 인덱스
+    PlaylistChanged,     // 재생 목록이 바뀜 (추가, 삭제, 정렬, 비우기)
+    PauseChanged(bool),
+    StopChanged(bool),
+    StopAfterCurrentChanged(bool),
+    PositionChanged {
+        position: Duration,
+        duration: Option<Duration>,
+    },
+    MusicErrorChanged {
+        file_path: PathBuf,
+        error: Option<String>, // None이면 다시 재생 가능한 곡
+    },
+    VolumeChanged(f32), // 실제로 출력하는 볼륨 (음소거 중이면 0)
+    RandomModeChanged(bool),
+    OutputDeviceLost,
+    OutputDeviceRestored,
+    ResumePositionChanged {
+        file_path: PathBuf,
+        position: Option<Duration>, // None이면 처음부터 재생
+    },
+    PlayCountChanged {
+        file_path: PathBuf,
+        play_count: u32,
+    },
+    MetadataLoaded, // 태그를 다 읽은 곡이 있음 (loaded_metadata에서 꺼내 재생 목록에 채운다)
+}
+
+/// 상태 이벤트 채널에 쌓아둘 수 있는 최대 이벤트 수 (느린 구독자는 오래된 이벤트를 건너뛴다)
+const STATUS_CHANNEL_CAPACITY: usize = 64;
+
+/// 백그라운드 루프와 공유하는 상태
+/// 값은 언제든 싸게 읽을 수 있는 스냅샷이고, 변화는 status_sender로 구독한다.
+#[derive(Debug, Clone)]
+pub struct BackgroundState {
+    pub current_index: Arc<AtomicUsize>, // Random 인덱스를 거치지 않은 순수한 1-N 인덱스
+    pub current_music_index: Arc<AtomicUsize>, // Random 인덱스를 거쳐서 실제 재생 대상을 가리키는 인덱스
+    pub is_random_mode: Arc<AtomicBool>,
+    pub is_paused: Arc<AtomicBool>,
+    pub is_stopped: Arc<AtomicBool>,
+    pub stop_after_current: Arc<AtomicBool>,
+    pub is_output_available: Arc<AtomicBool>, // 오디오 출력 장치를 열었는지 여부 (false면 Null 출력 사용 중)
+    pub broken_musics: Arc<Mutex<HashMap<PathBuf, String>>>, // 재생에 실패한 곡의 경로와 오류 메시지
+    pub position_millis: Arc<AtomicU64>,                     // 현재 곡의 재생 위치
+    pub duration_millis: Arc<AtomicU64>, // 현재 곡의 전체 길이 (0이면 알 수 없음)
+    pub loaded_metadata: Arc<Mutex<HashMap<PathBuf, MusicMetadata>>>, // 백그라운드에서 읽었지만 아직 재생 목록에 채우지 않은 태그
+    pub status_sender: broadcast::Sender<BackgroundStatusEvent>,
+}
+
+impl BackgroundState {
+    pub fn new(is_random_mode: bool) -> Self {
+        let (status_sender, _) = broadcast::channel(STATUS_CHANNEL_CAPACITY);
+
+        Self {
+            current_index: Default::default(),
+            current_music_index: Default::default(),
+            is_random_mode: Arc::new(is_random_mode.into()),
+            is_paused: Arc::new(false.into()),
+            is_stopped: Arc::new(false.into()),
+            stop_after_current: Arc::new(false.into()),
+            is_output_available: Arc::new(true.into()),
+            broken_musics: Default::default(),
+            position_millis: Default::default(),
+            duration_millis: Default::default(),
+            loaded_metadata: Default::default(),
+            status_sender,
+        }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<BackgroundStatusEvent> {
+        self.status_sender.subscribe()
+    }
+
+    /// 지금 상태부터 시작하는 상태 이벤트 스트림
+    pub fn status_stream(&self) -> StatusStream {
+        // 스냅샷을 만들기 전에 구독해야 그 사이에 발생한 이벤트를 놓치지 않는다.
+        let mut status_stream = self.status_changes();
+        status_stream.pending = status_stream.snapshot.events().into_iter();
+
+        status_stream
+    }
+
+    /// 지금 이후의 변화만 전달하는 상태 이벤트 스트림 (현재 상태는 따로 읽는 구독자용)
+    pub fn status_changes(&self) -> StatusStream {
+        StatusStream {
+            pending: Vec::new().into_iter(),
+            receiver: self.subscribe(),
+            snapshot: self.status_snapshot(),
+        }
+    }
+
+    /// 구독자가 없으면 이벤트는 그냥 버려진다.
+    pub fn publish(&self, event: BackgroundStatusEvent) {
+        let _ = self.status_sender.send(event);
+    }
+
+    /// 구독을 시작한 시점의 상태를 이벤트로 재구성한다.
+    /// 구독 이전에 발생한 이벤트는 받을 수 없으므로 첫 동기화에 사용한다.
+    pub fn snapshot_events(&self) -> Vec<BackgroundStatusEvent> {
+        self.status_snapshot().events()
+    }
+
+    fn status_snapshot(&self) -> StatusSnapshot {
+        StatusSnapshot {
+            current_music_index: self.current_music_index.clone(),
+            is_paused: self.is_paused.clone(),
+            is_stopped: self.is_stopped.clone(),
+            stop_after_current: self.stop_after_current.clone(),
+            is_output_available: self.is_output_available.clone(),
+            broken_musics: self.broken_musics.clone(),
+            loaded_metadata: self.loaded_metadata.clone(),
+        }
+    }
+}
+
+/// 상태 이벤트를 재구성하는 데 필요한 공유 상태
+/// 상태 이벤트 채널의 송신자는 잡지 않으므로, 백그라운드 루프가 끝나면 스트림도 끝난다.
+#[derive(Debug, Clone)]
+struct StatusSnapshot {
+    current_music_index: Arc<AtomicUsize>,
+    is_paused: Arc<AtomicBool>,
+    is_stopped: Arc<AtomicBool>,
+    stop_after_current: Arc<AtomicBool>,
+    is_output_available: Arc<AtomicBool>,
+    broken_musics: Arc<Mutex<HashMap<PathBuf, String>>>,
+    loaded_metadata: Arc<Mutex<HashMap<PathBuf, MusicMetadata>>>,
+}
+
+impl StatusSnapshot {
+    fn events(&self) -> Vec<BackgroundStatusEvent> {
+        use std::sync::atomic::Ordering;
+
+        let mut events = vec![
+            BackgroundStatusEvent::PlaylistChanged,
+            BackgroundStatusEvent::MusicChanged(self.current_music_index.load(Ordering::Acquire)),
+            BackgroundStatusEvent::PauseChanged(self.is_paused.load(Ordering::Acquire)),
+            BackgroundStatusEvent::StopChanged(self.is_stopped.load(Ordering::Acquire)),
+            BackgroundStatusEvent::StopAfterCurrentChanged(
+                self.stop_after_current.load(Ordering::Acquire),
+            ),
+        ];
+
+        if !self.is_output_available.load(Ordering::Acquire) {
+            events.push(BackgroundStatusEvent::OutputDeviceLost);
+        }
+
+        for (file_path, error) in self.broken_musics.lock().unwrap().iter() {
+            events.push(BackgroundStatusEvent::MusicErrorChanged {
+                file_path: file_path.clone(),
+                error: Some(error.clone()),
+            });
+        }
+
+        if !self.loaded_metadata.lock().unwrap().is_empty() {
+            events.push(BackgroundStatusEvent::MetadataLoaded);
+        }
+
+        events
+    }
+}
+
+/// 구독 시점의 상태를 재구성한 이벤트로 시작하고, 이후 변화를 이어서 전달하는 스트림
+/// 너무 밀려서 이벤트를 놓치면 그 시점의 상태를 다시 재구성해서 보낸다.
+/// 백그라운드 루프가 끝나면 None을 반환한다.
+pub struct StatusStream {
+    pending: std::vec::IntoIter<BackgroundStatusEvent>, // 채널보다 먼저 보낼 재구성한 이벤트
+    receiver: broadcast::Receiver<BackgroundStatusEvent>,
+    snapshot: StatusSnapshot,
+}
+
+impl StatusStream {
+    pub async fn recv(&mut self) -> Option<BackgroundStatusEvent> {
+        loop {
+            if let Some(event) = self.pending.next() {
+                return Some(event);
+            }
+
+            match self.receiver.recv().await {
+                Ok(event) => return Some(event),
+                Err(RecvError::Lagged(_)) => self.resync(),
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }
+
+    /// async 런타임 밖(스레드)에서 다음 이벤트를 기다린다.
+    pub fn blocking_recv(&mut self) -> Option<BackgroundStatusEvent> {
+        loop {
+            if let Some(event) = self.pending.next() {
+                return Some(event);
+            }
+
+            match self.receiver.blocking_recv() {
+                Ok(event) => return Some(event),
+                Err(RecvError::Lagged(_)) => self.resync(),
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }
+
+    /// 놓친 이벤트(곡 변경, 재생 오류 등)는 다시 받을 수 없으므로
+    /// 밀린 이벤트를 버리고 지금 상태를 재구성해서 다시 시작한다.
+    fn resync(&mut self) {
+        // 남아있는 오래된 이벤트가 재구성한 상태를 덮어쓰지 않도록 새로 구독한 뒤 재구성한다.
+        self.receiver = self.receiver.resubscribe();
+        self.pending = self.snapshot.events().into_iter();
+    }
+}
+
+impl Iterator for StatusStream {
+    type Item = BackgroundStatusEvent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.blocking_recv()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_stream_starts_from_snapshot() {
+        let background_state = BackgroundState::new(false);
+        background_state
+            .current_music_index
+            .store(2, std::sync::atomic::Ordering::Release);
+
+        let mut status_stream = background_state.status_stream();
+        background_state.publish(BackgroundStatusEvent::VolumeChanged(0.5));
+        drop(background_state);
+
+        let events = status_stream.by_ref().collect::<Vec<_>>();
+
+        assert_eq!(
+            vec![
+                BackgroundStatusEvent::PlaylistChanged,
+                BackgroundStatusEvent::MusicChanged(2),
+                BackgroundStatusEvent::PauseChanged(false),
+                BackgroundStatusEvent::StopChanged(false),
+                BackgroundStatusEvent::StopAfterCurrentChanged(false),
+                BackgroundStatusEvent::VolumeChanged(0.5),
+            ],
+            events
+        );
+    }
+
+    #[test]
+    fn status_stream_resyncs_after_lagging() {
+        let background_state = BackgroundState::new(false);
+        let mut status_stream = background_state.status_stream();
+
+        // 처음 스냅샷을 받은 뒤 채널 용량보다 많은 이벤트가 밀림
+        for _ in 0..5 {
+            status_stream.blocking_recv();
+        }
+
+        background_state.publish(BackgroundStatusEvent::MusicChanged(1));
+        background_state
+            .current_music_index
+            .store(1, std::sync::atomic::Ordering::Release);
+
+        for _ in 0..STATUS_CHANNEL_CAPACITY {
+            background_state.publish(BackgroundStatusEvent::PositionChanged {
+                position: Duration::ZERO,
+                duration: None,
+            });
+        }
+
+        background_state.publish(BackgroundStatusEvent::PauseChanged(true));
+        background_state
+            .is_paused
+            .store(true, std::sync::atomic::Ordering::Release);
+
+        // 놓친 MusicChanged(1)과 PauseChanged(true)는 재구성한 상태로 받는다.
+        assert_eq!(
+            Some(BackgroundStatusEvent::PlaylistChanged),
+            status_stream.blocking_recv()
+        );
+        assert_eq!(
+            Some(BackgroundStatusEvent::MusicChanged(1)),
+            status_stream.blocking_recv()
+        );
+        assert_eq!(
+            Some(BackgroundStatusEvent::PauseChanged(true)),
+            status_stream.blocking_recv()
+        );
+    }
+
+    #[test]
+    fn snapshot_reports_metadata_waiting_to_be_filled() {
+        let background_state = BackgroundState::new(false);
+        assert!(
+            !background_state
+                .snapshot_events()
+                .contains(&BackgroundStatusEvent::MetadataLoaded)
+        );
+
+        background_state
+            .loaded_metadata
+            .lock()
+            .unwrap()
+            .insert(PathBuf::from("/music/a"), MusicMetadata::default());
+
+        assert!(
+            background_state
+                .snapshot_events()
+                .contains(&BackgroundStatusEvent::MetadataLoaded)
+        );
+    }
+}
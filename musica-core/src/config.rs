@@ -0,0 +1,338 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::shortcut::KeyBindings;
+use crate::sort::SortOrder;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Config {
+    #[serde(default)]
+    pub is_random: bool, // 랜덤 재생 여부
+    #[serde(default)]
+    pub directory_path: PathBuf, // 재생할 기본 경로
+    #[serde(default = "Config::default_volume")]
+    pub volume: f32, // 볼륨 (0.0 - 1.0)
+    #[serde(default = "Config::default_resume_session")]
+    pub resume_session: bool, // 시작 시 마지막 세션 이어서 재생 여부
+    #[serde(default)]
+    pub start_paused: bool, // 세션을 이어서 재생할 때 일시정지 상태로 시작할지 여부
+    #[serde(default = "Config::default_resume_position_min_secs")]
+    pub resume_position_min_secs: u64, // 이보다 긴 곡은 곡별 재생 위치를 기억 (오디오북, 팟캐스트 등)
+    #[serde(default)]
+    pub key_bindings: KeyBindings, // 단축키 (지정하지 않은 동작은 기본값 사용)
+    #[serde(default)]
+    pub sort_order: SortOrder, // 재생 목록 정렬 기준
+    #[serde(default)]
+    pub window: WindowGeometry, // 마지막 창 크기와 위치
+    #[serde(default = "Config::default_theme")]
+    pub theme: String, // iced 내장 테마 이름 또는 "System" (시스템 라이트/다크 설정을 따름)
+    #[serde(default)]
+    pub custom_colors: CustomColors, // 테마 색상 덮어쓰기
+    #[serde(default)]
+    pub api: ApiConfig, // 로컬 HTTP API 서버
+    #[serde(default)]
+    pub mpd: MpdConfig, // MPD 프로토콜 서버
+}
+
+/// 다른 프로그램에서 재생을 제어하기 위한 로컬 HTTP/WebSocket API 설정
+/// localhost에만 열리고, 요청마다 token과 같은 토큰을 요구한다.
+/// 브라우저 페이지에서는 allowed_origins에 있는 Origin(예: "http://localhost:8080")만 호출할 수 있다.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ApiConfig {
+    pub enabled: bool,
+    pub port: u16,
+    pub token: Option<String>, // 없으면 처음 켤 때 만들어서 저장
+    pub allowed_origins: Vec<String>,
+}
+
+impl ApiConfig {
+    pub const DEFAULT_PORT: u16 = 7700;
+
+    /// 추측할 수 없는 임의의 토큰(128비트, 16진수)을 만든다.
+    pub fn generate_token() -> String {
+        rand::random::<[u8; 16]>()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+}
+
+/// MPD 클라이언트(mpc, ncmpcpp 등)로 제어하기 위한 MPD 프로토콜 서버 설정 (localhost에만 열림)
+/// HTTP API 토큰이 있으면 MPD의 password 명령으로 같은 토큰을 보내야 제어할 수 있다.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct MpdConfig {
+    pub enabled: bool,
+    pub port: u16,
+}
+
+impl MpdConfig {
+    pub const DEFAULT_PORT: u16 = 6600;
+}
+
+impl Default for MpdConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: Self::DEFAULT_PORT,
+        }
+    }
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: Self::DEFAULT_PORT,
+            token: None,
+            allowed_origins: Vec::new(),
+        }
+    }
+}
+
+/// 테마 위에 덮어쓸 사용자 색상 ("#rrggbb" 형식, None이면 테마 색상 사용)
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct CustomColors {
+    pub accent: Option<String>,
+    pub background: Option<String>,
+}
+
+/// 창 크기와 위치 (논리 픽셀)
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct WindowGeometry {
+    pub width: f32,
+    pub height: f32,
+    pub x: Option<f32>, // None이면 운영체제가 위치를 정함
+    pub y: Option<f32>,
+}
+
+impl WindowGeometry {
+    pub const MIN_WIDTH: f32 = 300.0;
+    pub const MIN_HEIGHT: f32 = 400.0;
+}
+
+impl Default for WindowGeometry {
+    fn default() -> Self {
+        Self {
+            width: 300.0,
+            height: 600.0,
+            x: None,
+            y: None,
+        }
+    }
+}
+
+impl Config {
+    pub const DEFAULT_VOLUME: f32 = 1.0;
+    pub const DEFAULT_RESUME_POSITION_MIN_SECS: u64 = 20 * 60;
+    pub const DEFAULT_THEME: &str = "Dracula";
+
+    fn default_volume() -> f32 {
+        Self::DEFAULT_VOLUME
+    }
+
+    fn default_theme() -> String {
+        Self::DEFAULT_THEME.into()
+    }
+
+    fn default_resume_session() -> bool {
+        true
+    }
+
+    fn default_resume_position_min_secs() -> u64 {
+        Self::DEFAULT_RESUME_POSITION_MIN_SECS
+    }
+
+    pub fn normalize_volume(volume: f32) -> f32 {
+        volume.clamp(0.0, 1.0)
+    }
+
+    pub fn update_config_if_exists(&self, path: PathBuf) -> anyhow::Result<()> {
+        let mut config = self.clone();
+        config.volume = Self::normalize_volume(config.volume);
+
+        let config_str = serde_json::to_string(&config)?;
+
+        fs::write(path, config_str)?;
+
+        Ok(())
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            is_random: false,
+            directory_path: PathBuf::default(),
+            volume: Self::DEFAULT_VOLUME,
+            resume_session: Self::default_resume_session(),
+            start_paused: false,
+            resume_position_min_secs: Self::DEFAULT_RESUME_POSITION_MIN_SECS,
+            key_bindings: KeyBindings::default(),
+            sort_order: SortOrder::default(),
+            window: WindowGeometry::default(),
+            theme: Self::default_theme(),
+            custom_colors: CustomColors::default(),
+            api: ApiConfig::default(),
+            mpd: MpdConfig::default(),
+        }
+    }
+}
+
+/// 명령줄 옵션 등으로 덮어쓰기 전의 설정 값 (설정 파일에 저장할 값)
+/// 화면에서 같은 설정을 직접 바꾸면 그 값을 저장하도록 None으로 비운다.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigOverrides {
+    pub is_random: Option<bool>,
+    pub volume: Option<f32>,
+}
+
+impl ConfigOverrides {
+    /// 설정 파일의 값 중 지정한 값을 덮어쓴다.
+    /// 덮어쓰기 전의 값을 반환해서 설정을 저장할 때 되돌릴 수 있게 한다.
+    pub fn apply(config: &mut Config, is_random: Option<bool>, volume: Option<f32>) -> Self {
+        let mut overrides = Self::default();
+
+        if let Some(is_random) = is_random {
+            overrides.is_random = Some(config.is_random);
+            config.is_random = is_random;
+        }
+
+        if let Some(volume) = volume {
+            overrides.volume = Some(config.volume);
+            config.volume = volume;
+        }
+
+        overrides
+    }
+
+    pub fn restore(&self, config: &mut Config) {
+        if let Some(is_random) = self.is_random {
+            config.is_random = is_random;
+        }
+
+        if let Some(volume) = self.volume {
+            config.volume = volume;
+        }
+    }
+}
+
+pub fn get_app_data_path() -> PathBuf {
+    // ------- Windows Only
+    #[cfg(target_os = "windows")]
+    let app_data_path = PathBuf::from(r"\AppData\Local\musica");
+    // Windows Only -------
+
+    #[cfg(not(any(target_os = "windows")))]
+    let home_dir = env::var("HOME").unwrap_or_else(|_| String::from(""));
+
+    #[cfg(target_os = "macos")]
+    let app_data_path = PathBuf::from(format!("{}/Library/Application Support/musica", home_dir));
+
+    #[cfg(target_os = "linux")]
+    let app_data_path = PathBuf::from(format!("{}/.local/share/musica", home_dir));
+
+    // Ensure the directory exists
+    if !app_data_path.exists() {
+        fs::create_dir_all(&app_data_path).expect("Failed to create app data directory");
+    }
+
+    app_data_path
+}
+
+pub fn get_config_path() -> PathBuf {
+    let app_data_path = get_app_data_path();
+
+    app_data_path.join("config.json")
+}
+
+pub fn create_config_if_not_exists(path: PathBuf) -> anyhow::Result<()> {
+    if !path.exists() {
+        let config = Config {
+            directory_path: env::current_dir()?,
+            ..Default::default()
+        };
+
+        let config_str = serde_json::to_string(&config)?;
+
+        fs::write(path, config_str)?;
+
+        Ok(())
+    } else {
+        Ok(())
+    }
+}
+
+pub fn read_config_if_exists(path: PathBuf) -> anyhow::Result<Config> {
+    let config_str = fs::read_to_string(path)?;
+
+    let mut config: Config = serde_json::from_str(&config_str)?;
+    config.volume = Config::normalize_volume(config.volume);
+
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserializes_legacy_config_with_default_volume() {
+        let config: Config =
+            serde_json::from_str(r#"{"is_random":true,"directory_path":"/tmp/music"}"#).unwrap();
+
+        assert_eq!(Config::DEFAULT_VOLUME, config.volume);
+        assert!(config.resume_session);
+        assert!(!config.start_paused);
+        assert_eq!(KeyBindings::default(), config.key_bindings);
+        assert_eq!(SortOrder::FileName, config.sort_order);
+        assert_eq!(WindowGeometry::default(), config.window);
+        assert_eq!(Config::DEFAULT_THEME, config.theme);
+        assert_eq!(ApiConfig::default(), config.api);
+        assert_eq!(MpdConfig::default(), config.mpd);
+    }
+
+    #[test]
+    fn generates_distinct_api_tokens() {
+        let token = ApiConfig::generate_token();
+
+        assert_eq!(32, token.len());
+        assert!(token.chars().all(|char| char.is_ascii_hexdigit()));
+        assert_ne!(token, ApiConfig::generate_token());
+    }
+
+    #[test]
+    fn default_config_uses_default_volume() {
+        assert_eq!(Config::DEFAULT_VOLUME, Config::default().volume);
+    }
+
+    #[test]
+    fn clamps_volume_to_supported_range() {
+        assert_eq!(0.0, Config::normalize_volume(-0.5));
+        assert_eq!(0.5, Config::normalize_volume(0.5));
+        assert_eq!(1.0, Config::normalize_volume(1.5));
+    }
+
+    #[test]
+    fn overrides_are_restored_before_saving() {
+        let mut config = Config {
+            is_random: true,
+            volume: 0.8,
+            ..Default::default()
+        };
+
+        let overrides = ConfigOverrides::apply(&mut config, Some(false), Some(0.2));
+
+        assert!(!config.is_random);
+        assert_eq!(0.2, config.volume);
+
+        overrides.restore(&mut config);
+
+        assert!(config.is_random);
+        assert_eq!(0.8, config.volume);
+    }
+}
@@ -0,0 +1,157 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::sort;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct FileInfo {
+    pub filename: String,
+    pub filepath: PathBuf,
+    pub added_at: Option<SystemTime>, // 생성 시각 (지원하지 않는 파일 시스템이면 수정 시각)
+}
+
+impl FileInfo {
+    /// 파일 하나의 정보를 읽는다. (폴더 등 일반 파일이 아니면 오류)
+    pub fn from_path(path: &Path) -> anyhow::Result<Self> {
+        let metadata = fs::metadata(path)?;
+
+        if !metadata.is_file() {
+            anyhow::bail!("Not a file: {}", path.display());
+        }
+
+        let filename = path
+            .file_name()
+            .and_then(|file_name| file_name.to_str())
+            .unwrap_or("error")
+            .to_owned();
+        let added_at = metadata.created().or_else(|_| metadata.modified()).ok();
+
+        Ok(Self {
+            filename,
+            filepath: path.to_path_buf(),
+            added_at,
+        })
+    }
+
+    pub fn is_music_file(&self) -> bool {
+        let ext = self.filepath.extension().unwrap_or_default();
+        ext == "mp3" || ext == "ogg" || ext == "flac"
+    }
+}
+
+/// 폴더, 음악 파일, 재생 목록이 섞인 경로에서 파일 목록을 만든다. (경로 순서 유지, 폴더는 파일 이름 순)
+pub fn read_paths(paths: &[PathBuf]) -> Vec<FileInfo> {
+    let mut file_list = vec![];
+
+    for path in paths {
+        let result = if path.is_dir() {
+            read_file_list(path)
+        } else if is_playlist_file(path) {
+            read_playlist(path).map(|music_paths| {
+                music_paths
+                    .iter()
+                    .filter_map(|music_path| FileInfo::from_path(music_path).ok())
+                    .collect()
+            })
+        } else {
+            FileInfo::from_path(path).map(|file_info| vec![file_info])
+        };
+
+        match result {
+            Ok(files) => file_list.extend(files),
+            Err(error) => log::error!("Failed to read {}: {:?}", path.display(), error),
+        }
+    }
+
+    file_list
+}
+
+/// .m3u/.m3u8 재생 목록 파일인지 확인한다.
+pub fn is_playlist_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("m3u") || ext.eq_ignore_ascii_case("m3u8"))
+}
+
+/// m3u 재생 목록의 곡 경로를 순서대로 읽는다.
+/// 주석(#EXTINF 등)과 빈 줄은 건너뛰고, 상대 경로는 재생 목록 파일 위치를 기준으로 한다.
+pub fn read_playlist(path: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let playlist = fs::read_to_string(path)?;
+    let base_path = path.parent().unwrap_or(Path::new(""));
+
+    let music_paths = playlist
+        .lines()
+        .map(|line| line.trim_start_matches('\u{feff}').trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| base_path.join(line))
+        .collect();
+
+    Ok(music_paths)
+}
+
+pub fn read_file_list(path: &Path) -> anyhow::Result<Vec<FileInfo>> {
+    let read_dir_result = fs::read_dir(path)?;
+
+    let mut file_list = read_dir_result
+        .flatten()
+        .filter_map(|read_dir_entry| FileInfo::from_path(&read_dir_entry.path()).ok())
+        .collect::<Vec<_>>();
+
+    // read_dir 순서는 파일 시스템마다 다르므로 파일 이름 순으로 맞춘다.
+    file_list.sort_by(|a, b| sort::natural_cmp(&a.filename, &b.filename));
+
+    Ok(file_list)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_only_files_in_natural_order() {
+        let directory =
+            std::env::temp_dir().join(format!("musica-file-list-{}", std::process::id()));
+        fs::create_dir_all(directory.join("sub.mp3")).unwrap();
+        fs::write(directory.join("Track 10.mp3"), b"").unwrap();
+        fs::write(directory.join("Track 2.ogg"), b"").unwrap();
+        fs::write(directory.join("cover.jpg"), b"").unwrap();
+
+        let file_list = read_file_list(&directory).unwrap();
+        let music_files = file_list
+            .iter()
+            .filter(|file_info| file_info.is_music_file())
+            .map(|file_info| file_info.filename.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(vec!["Track 2.ogg", "Track 10.mp3"], music_files);
+        assert!(FileInfo::from_path(&directory.join("sub.mp3")).is_err());
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn reads_playlist_relative_to_its_directory() {
+        let directory =
+            std::env::temp_dir().join(format!("musica-playlist-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+
+        let playlist_path = directory.join("list.M3U");
+        fs::write(
+            &playlist_path,
+            "\u{feff}#EXTM3U\n#EXTINF:123,Artist - Title\nalbum/01.mp3\n\n/absolute/02.ogg\r\n",
+        )
+        .unwrap();
+
+        assert!(is_playlist_file(&playlist_path));
+        assert_eq!(
+            vec![
+                directory.join("album/01.mp3"),
+                PathBuf::from("/absolute/02.ogg")
+            ],
+            read_playlist(&playlist_path).unwrap()
+        );
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -0,0 +1,17 @@
+//! musica의 재생 엔진
+//! 설정, 재생 목록, 백그라운드 재생 루프를 가지고 있으며, 화면(iced, TUI)이나 원격 제어 없이도
+//! [`player::Player`]로 재생을 제어하고 [`player::Player::status_stream`]으로 상태 변화를 받을 수 있다.
+//! 오디오 출력은 [`player::PlayerOptions::open_device`]로 바꿀 수 있고, `test-util` 기능을 켜면
+//! 장치 없이 재생 순서를 확인하는 `backend::output::fake::FakeOutput`을 쓸 수 있다.
+
+pub mod backend;
+pub mod config;
+pub mod file;
+pub mod metadata;
+pub mod play_count;
+pub mod player;
+pub mod resume_position;
+pub mod session;
+pub mod shortcut;
+pub mod sort;
+pub mod state;
@@ -0,0 +1,126 @@
+use std::fs::File;
+use std::path::Path;
+use std::time::Duration;
+
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::{
+    MetadataOptions, MetadataRevision, StandardTagKey, StandardVisualKey, Visual,
+};
+use symphonia::core::probe::{Hint, ProbeResult};
+
+/// 음악 파일에 들어있는 태그 정보 (ID3, Vorbis Comment, MP4 등)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MusicMetadata {
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub track_number: Option<u32>,
+    pub duration: Option<Duration>,
+}
+
+impl MusicMetadata {
+    /// 이미 채워진 값은 유지하고 비어있는 값만 revision의 태그로 채운다.
+    fn fill_from(&mut self, revision: &MetadataRevision) {
+        for tag in revision.tags() {
+            let value = tag.value.to_string();
+            let value = value.trim();
+
+            let field = match tag.std_key {
+                Some(StandardTagKey::TrackTitle) => &mut self.title,
+                Some(StandardTagKey::Artist) => &mut self.artist,
+                Some(StandardTagKey::Album) => &mut self.album,
+                Some(StandardTagKey::TrackNumber) => {
+                    if self.track_number.is_none() {
+                        self.track_number = parse_track_number(value);
+                    }
+                    continue;
+                }
+                _ => continue,
+            };
+
+            if field.is_none() && !value.is_empty() {
+                *field = Some(value.to_string());
+            }
+        }
+    }
+}
+
+/// 파일의 헤더만 읽어서 태그 정보를 가져온다. (디코딩은 하지 않음)
+pub fn read_metadata(path: &Path) -> anyhow::Result<MusicMetadata> {
+    let mut probed = probe(path)?;
+
+    let mut metadata = MusicMetadata {
+        duration: probed.format.default_track().and_then(|track| {
+            let time_base = track.codec_params.time_base?;
+            let frames = track.codec_params.n_frames?;
+            let time = time_base.calc_time(frames);
+
+            Some(Duration::from_secs(time.seconds) + Duration::from_secs_f64(time.frac))
+        }),
+        ..Default::default()
+    };
+
+    // 컨테이너 자체의 태그를 우선하고, 그 앞에 붙은 태그(ID3v2 등)로 나머지를 채운다.
+    if let Some(revision) = probed.format.metadata().current() {
+        metadata.fill_from(revision);
+    }
+
+    if let Some(probed_metadata) = probed.metadata.get()
+        && let Some(revision) = probed_metadata.current()
+    {
+        metadata.fill_from(revision);
+    }
+
+    Ok(metadata)
+}
+
+/// 파일에 들어있는 앨범 커버 이미지(ID3 APIC, FLAC/Vorbis METADATA_BLOCK_PICTURE 등)를 읽는다.
+/// 앞표지가 있으면 앞표지를, 없으면 첫 번째 이미지를 사용한다.
+pub fn read_embedded_picture(path: &Path) -> anyhow::Result<Option<Vec<u8>>> {
+    let mut probed = probe(path)?;
+
+    let mut visuals: Vec<Visual> = vec![];
+
+    if let Some(revision) = probed.format.metadata().current() {
+        visuals.extend(revision.visuals().iter().cloned());
+    }
+
+    if let Some(probed_metadata) = probed.metadata.get()
+        && let Some(revision) = probed_metadata.current()
+    {
+        visuals.extend(revision.visuals().iter().cloned());
+    }
+
+    let picture = visuals
+        .iter()
+        .find(|visual| visual.usage == Some(StandardVisualKey::FrontCover))
+        .or(visuals.first())
+        .map(|visual| visual.data.to_vec());
+
+    Ok(picture)
+}
+
+fn probe(path: &Path) -> anyhow::Result<ProbeResult> {
+    let file = File::open(path)?;
+    let media_source_stream = MediaSourceStream::new(Box::new(file), Default::default());
+
+    let mut hint = Hint::new();
+    if let Some(extension) = path.extension().and_then(|extension| extension.to_str()) {
+        hint.with_extension(extension);
+    }
+
+    let probed = symphonia::default::get_probe().format(
+        &hint,
+        media_source_stream,
+        &FormatOptions::default(),
+        &MetadataOptions::default(),
+    )?;
+
+    Ok(probed)
+}
+
+/// "3" 또는 "3/12" 형식의 트랙 번호를 읽는다.
+fn parse_track_number(value: &str) -> Option<u32> {
+    value.split('/').next()?.trim().parse().ok()
+}
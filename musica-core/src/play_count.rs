@@ -0,0 +1,44 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 곡별 재생 횟수 (재생 횟수 순 정렬에 사용)
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PlayCounts {
+    #[serde(default)]
+    pub counts: HashMap<PathBuf, u32>, // 곡 경로 -> 재생을 시작한 횟수
+}
+
+impl PlayCounts {
+    pub fn get(&self, file_path: &Path) -> u32 {
+        self.counts.get(file_path).copied().unwrap_or_default()
+    }
+
+    /// 재생 횟수를 하나 늘리고 늘어난 값을 반환한다.
+    pub fn increment(&mut self, file_path: PathBuf) -> u32 {
+        let count = self.counts.entry(file_path).or_default();
+        *count = count.saturating_add(1);
+
+        *count
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let counts_str = serde_json::to_string(self)?;
+
+        fs::write(path, counts_str)?;
+
+        Ok(())
+    }
+}
+
+pub fn get_play_counts_path(app_data_path: &Path) -> PathBuf {
+    app_data_path.join("play_counts.json")
+}
+
+pub fn read_play_counts_if_exists(path: PathBuf) -> anyhow::Result<PlayCounts> {
+    let counts_str = fs::read_to_string(path)?;
+
+    let counts: PlayCounts = serde_json::from_str(&counts_str)?;
+
+    Ok(counts)
+}
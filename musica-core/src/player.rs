@@ -0,0 +1,618 @@
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Sender};
+use std::time::{Duration, Instant};
+
+use crate::backend::output::{self, OpenDeviceFn};
+use crate::backend::state::{
+    BackgroundLoopEvent, BackgroundState, BackgroundStatusEvent, StatusStream,
+};
+use crate::backend::{BackgroundLoopOptions, background_loop};
+use crate::config::{self, Config, ConfigOverrides};
+use crate::file::{self, FileInfo};
+use crate::session::{self, ResumePoint};
+use crate::sort::{self, SortOrder};
+use crate::state::{MainState, Music, MusicList};
+use crate::{metadata, play_count, resume_position};
+
+/// 종료 시 세션 저장을 기다리는 최대 시간
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// 태그를 읽는 동안 읽은 만큼 재생 목록에 채우는 간격
+const METADATA_PUBLISH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// 재생 엔진 시작 옵션
+#[derive(Debug, Clone)]
+pub struct PlayerOptions {
+    pub config_path: PathBuf,
+    pub paths: Vec<PathBuf>, // 지정하면 설정의 음악 폴더와 지난 세션 대신 받은 순서대로 재생
+    pub is_random: Option<bool>, // 설정 파일 대신 쓸 값 (저장하지 않음)
+    pub volume: Option<f32>,
+    pub no_autoplay: bool,         // 첫 곡을 일시정지 상태로 연다.
+    pub open_device: OpenDeviceFn, // 오디오 출력을 여는 함수 (기본 출력 장치 대신 다른 출력을 쓸 때 교체)
+    pub app_data_path: PathBuf,    // 세션, 재생 위치, 재생 횟수를 저장할 폴더
+}
+
+impl Default for PlayerOptions {
+    fn default() -> Self {
+        Self {
+            config_path: config::get_config_path(),
+            paths: vec![],
+            is_random: None,
+            volume: None,
+            no_autoplay: false,
+            open_device: output::open_device_output,
+            app_data_path: config::get_app_data_path(),
+        }
+    }
+}
+
+/// 화면과 상관없는 재생기 상태
+/// 설정과 재생 목록을 가지고 백그라운드 루프를 제어한다. iced 화면, TUI, 헤드리스 모드가 함께 사용한다.
+/// 재생 상태의 변화는 status_stream으로 받아서 handle_status로 다시 넘겨준다.
+pub struct Player {
+    pub main_state: MainState,
+    pub config_data: Config,
+    config_path: PathBuf,
+    config_overrides: ConfigOverrides, // 옵션으로 덮어쓰기 전의 설정 값
+    app_data_path: PathBuf,
+
+    background_event_sender: Sender<BackgroundLoopEvent>,
+    background_state: BackgroundState,
+}
+
+impl Player {
+    /// 설정과 재생 목록을 읽고 백그라운드 루프를 시작한다.
+    pub fn new(options: PlayerOptions) -> Self {
+        let config_path = options.config_path;
+        let mut config_data =
+            config::read_config_if_exists(config_path.clone()).unwrap_or_default();
+        let config_overrides =
+            ConfigOverrides::apply(&mut config_data, options.is_random, options.volume);
+
+        let (sender, receiver) = mpsc::channel::<BackgroundLoopEvent>();
+
+        let background_state = BackgroundState::new(config_data.is_random);
+
+        let mut player = Self {
+            main_state: MainState {
+                title: "no music".into(),
+                music_list: MusicList::default(),
+                on_play: true,
+                is_stopped: false,
+                stop_after_current: false,
+                is_output_available: true,
+                is_muted: false,
+                current_music_index: 0,
+                position: Duration::ZERO,
+                duration: None,
+                album_art_path: None,
+            },
+            config_data,
+            config_path,
+            config_overrides,
+            app_data_path: options.app_data_path,
+            background_state,
+            background_event_sender: sender,
+        };
+
+        player.config_data.volume = Config::normalize_volume(player.config_data.volume);
+
+        let resume_point = if !options.paths.is_empty() {
+            // 지정한 파일을 받은 순서대로 재생
+            player.main_state.music_list.list =
+                read_music_list(&file::read_paths(&options.paths), &player.app_data_path);
+            player.load_metadata(&player.main_state.music_list.list);
+            None
+        } else {
+            player.update_music_list_from_config();
+
+            // 지난 세션의 재생 목록 순서와 재생 위치를 복원
+            if player.config_data.resume_session
+                && let Ok(session) = session::read_session_if_exists(session::get_session_path(
+                    &player.app_data_path,
+                ))
+            {
+                session.restore_queue_order(&mut player.main_state.music_list);
+                session.resume_point(
+                    &player.main_state.music_list,
+                    player.config_data.start_paused,
+                )
+            } else {
+                None
+            }
+        };
+
+        // no_autoplay: 이어서 재생할 지점이 없으면 첫 곡을 일시정지 상태로 연다.
+        let resume_point = match resume_point {
+            Some(resume_point) if options.no_autoplay => Some(ResumePoint {
+                is_paused: true,
+                ..resume_point
+            }),
+            None if options.no_autoplay => Some(ResumePoint {
+                music_index: 0,
+                position: Duration::ZERO,
+                is_paused: true,
+                random_indices: None,
+            }),
+            resume_point => resume_point,
+        };
+
+        let music_list = player.main_state.music_list.clone();
+
+        background_loop(
+            receiver,
+            player.background_state.clone(),
+            music_list,
+            BackgroundLoopOptions {
+                initial_volume: player.config_data.volume,
+                open_device: options.open_device,
+                resume_point,
+                resume_position_min_duration: Duration::from_secs(
+                    player.config_data.resume_position_min_secs,
+                ),
+                app_data_path: player.app_data_path.clone(),
+            },
+        );
+
+        // 백그라운드 스레드가 OutputStream/Sink를 생성한 뒤 첫 곡을 자동 재생하도록 트리거
+        player
+            .background_event_sender
+            .send(BackgroundLoopEvent::StartUp)
+            .unwrap();
+
+        player
+    }
+
+    /// 백그라운드 루프와 공유하는 상태 (원격 제어 서버가 현재 상태를 읽는 데 사용)
+    pub fn background_state(&self) -> &BackgroundState {
+        &self.background_state
+    }
+
+    /// 지금 재생 상태부터 시작하는 상태 이벤트 스트림
+    pub fn status_stream(&self) -> StatusStream {
+        self.background_state.status_stream()
+    }
+
+    pub fn toggle_pause(&mut self) {
+        if self.main_state.on_play {
+            if let Err(error) = self
+                .background_event_sender
+                .send(BackgroundLoopEvent::Pause)
+            {
+                log::error!("Failed to send event: {:?}", error);
+            }
+
+            self.main_state.on_play = false;
+        } else {
+            if let Err(error) = self
+                .background_event_sender
+                .send(BackgroundLoopEvent::Resume)
+            {
+                log::error!("Failed to send event: {:?}", error);
+            }
+
+            self.main_state.on_play = true;
+        }
+    }
+
+    pub fn stop(&mut self) {
+        if let Err(error) = self.background_event_sender.send(BackgroundLoopEvent::Stop) {
+            log::error!("Failed to send event: {:?}", error);
+        }
+
+        self.main_state.on_play = false;
+    }
+
+    pub fn set_stop_after_current(&mut self, flag: bool) {
+        if let Err(error) = self
+            .background_event_sender
+            .send(BackgroundLoopEvent::StopAfterCurrent(flag))
+        {
+            log::error!("Failed to send event: {:?}", error);
+        }
+
+        self.main_state.stop_after_current = flag;
+    }
+
+    pub fn next(&self) {
+        if let Err(error) = self.background_event_sender.send(BackgroundLoopEvent::Next) {
+            log::error!("Failed to send event: {:?}", error);
+        }
+    }
+
+    pub fn previous(&self) {
+        if let Err(error) = self
+            .background_event_sender
+            .send(BackgroundLoopEvent::Previous)
+        {
+            log::error!("Failed to send event: {:?}", error);
+        }
+    }
+
+    /// 목록의 index번째 곡을 바로 재생한다.
+    pub fn play_index(&self, index: usize) {
+        if let Err(error) = self
+            .background_event_sender
+            .send(BackgroundLoopEvent::DirectPlayMusic(index))
+        {
+            log::error!("Failed to send event: {:?}", error);
+        }
+    }
+
+    pub fn seek_by(&self, offset_millis: i64) {
+        if let Err(error) = self
+            .background_event_sender
+            .send(BackgroundLoopEvent::SeekBy(offset_millis))
+        {
+            log::error!("Failed to send event: {:?}", error);
+        }
+    }
+
+    pub fn set_random_mode(&mut self, flag: bool) {
+        self.config_data.is_random = flag;
+        self.config_overrides.is_random = None;
+
+        self.save_config();
+
+        if let Err(error) = self
+            .background_event_sender
+            .send(BackgroundLoopEvent::RandomModeChanged(flag))
+        {
+            log::error!("Failed to send event: {:?}", error);
+        }
+    }
+
+    pub fn set_volume(&mut self, volume: f32) {
+        let volume = Config::normalize_volume(volume);
+        self.config_data.volume = volume;
+        self.config_overrides.volume = None;
+        // 볼륨을 직접 조절하면 음소거를 해제
+        self.main_state.is_muted = false;
+
+        self.save_config();
+
+        if let Err(error) = self
+            .background_event_sender
+            .send(BackgroundLoopEvent::VolumeChanged(volume))
+        {
+            log::error!("Failed to send event: {:?}", error);
+        }
+    }
+
+    pub fn toggle_mute(&mut self) {
+        self.main_state.is_muted = !self.main_state.is_muted;
+
+        let volume = if self.main_state.is_muted {
+            0.0
+        } else {
+            self.config_data.volume
+        };
+
+        if let Err(error) = self
+            .background_event_sender
+            .send(BackgroundLoopEvent::VolumeChanged(volume))
+        {
+            log::error!("Failed to send event: {:?}", error);
+        }
+    }
+
+    /// 이보다 긴 곡만 곡별 재생 위치를 기억한다.
+    pub fn set_resume_position_min_secs(&mut self, secs: u64) {
+        self.config_data.resume_position_min_secs = secs;
+
+        self.save_config();
+
+        if let Err(error) = self.background_event_sender.send(
+            BackgroundLoopEvent::ResumePositionMinDurationChanged(Duration::from_secs(secs)),
+        ) {
+            log::error!("Failed to send event: {:?}", error);
+        }
+    }
+
+    pub fn set_sort_order(&mut self, sort_order: SortOrder) {
+        self.config_data.sort_order = sort_order;
+
+        self.save_config();
+
+        sort::sort_music_list(&mut self.main_state.music_list, sort_order);
+        self.send_music_list_to_background();
+    }
+
+    /// 음악 폴더를 바꾸고 재생 목록을 그 폴더의 곡으로 다시 만든다.
+    pub fn set_music_directory(&mut self, path: PathBuf) {
+        self.config_data.directory_path = path;
+
+        self.save_config();
+
+        self.update_music_list_from_config();
+        self.send_music_list_to_background();
+    }
+
+    pub fn update_music_list_from_config(&mut self) {
+        let music_directory_path = self.config_data.directory_path.clone();
+
+        if let Ok(file_info_list) = file::read_file_list(&music_directory_path) {
+            self.main_state.music_list.list = read_music_list(&file_info_list, &self.app_data_path);
+            self.load_metadata(&self.main_state.music_list.list);
+
+            sort::sort_music_list(&mut self.main_state.music_list, self.config_data.sort_order);
+        }
+    }
+
+    /// 목록에 없는 음악 파일을 추가하고 다시 정렬한다. 추가한 곡의 경로를 반환한다.
+    pub fn add_music_files(&mut self, file_info_list: &[FileInfo]) -> Vec<PathBuf> {
+        let new_file_info_list = file_info_list
+            .iter()
+            .filter(|file_info| {
+                !self
+                    .main_state
+                    .music_list
+                    .list
+                    .iter()
+                    .any(|music| music.file_path == file_info.filepath)
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let new_music_list = read_music_list(&new_file_info_list, &self.app_data_path);
+        self.load_metadata(&new_music_list);
+        let added_paths = new_music_list
+            .iter()
+            .map(|music| music.file_path.clone())
+            .collect();
+
+        self.main_state.music_list.list.extend(new_music_list);
+        sort::sort_music_list(&mut self.main_state.music_list, self.config_data.sort_order);
+
+        added_paths
+    }
+
+    /// 다른 실행에서 넘겨받은 파일/폴더/재생 목록을 재생 목록에 추가한다.
+    /// enqueue가 아니면 그중 첫 곡을 바로 재생한다. (상대 경로는 음악 폴더 기준)
+    pub fn open_paths(&mut self, paths: &[PathBuf], enqueue: bool) {
+        let paths = paths
+            .iter()
+            .map(|path| self.config_data.directory_path.join(path))
+            .collect::<Vec<_>>();
+        let file_info_list = file::read_paths(&paths);
+
+        self.add_music_files(&file_info_list);
+        self.send_music_list_to_background();
+
+        if !enqueue && let Some(file_info) = file_info_list.iter().find(|x| x.is_music_file()) {
+            self.play_file(&file_info.filepath);
+        }
+    }
+
+    /// 목록에 있는 곡을 바로 재생한다. (이미 목록에 있던 곡이어도 재생)
+    pub fn play_file(&self, file_path: &Path) {
+        if let Some(index) = self
+            .main_state
+            .music_list
+            .list
+            .iter()
+            .position(|music| music.file_path == file_path)
+        {
+            self.play_index(index);
+        }
+    }
+
+    /// 백그라운드 루프의 상태 이벤트로 재생 상태를 맞춘다.
+    pub fn handle_status(&mut self, status: &BackgroundStatusEvent) {
+        match status {
+            BackgroundStatusEvent::MusicChanged(index) => {
+                if let Some(music) = self.main_state.music_list.list.get(*index) {
+                    self.main_state.title = music.title.clone();
+                }
+
+                self.main_state.current_music_index = *index;
+            }
+            BackgroundStatusEvent::PauseChanged(is_paused) => {
+                // 백엔드의 실제 일시정지 상태를 프론트엔드 on_play와 동기화
+                self.main_state.on_play = !is_paused;
+            }
+            BackgroundStatusEvent::StopChanged(is_stopped) => {
+                self.main_state.is_stopped = *is_stopped;
+            }
+            BackgroundStatusEvent::StopAfterCurrentChanged(stop_after_current) => {
+                self.main_state.stop_after_current = *stop_after_current;
+            }
+            BackgroundStatusEvent::PositionChanged { position, duration } => {
+                self.main_state.position = *position;
+                self.main_state.duration = *duration;
+            }
+            BackgroundStatusEvent::MusicErrorChanged { file_path, error } => {
+                // 백엔드에서 재생에 실패한 곡을 목록에 표시
+                if let Some(music) = self.find_music_mut(file_path) {
+                    music.error = error.clone();
+                }
+            }
+            // 여기서 보낸 변경이 그대로 돌아온 것이므로 할 일이 없다.
+            BackgroundStatusEvent::PlaylistChanged
+            | BackgroundStatusEvent::VolumeChanged(_)
+            | BackgroundStatusEvent::RandomModeChanged(_) => {}
+            BackgroundStatusEvent::OutputDeviceLost => {
+                self.main_state.is_output_available = false;
+            }
+            BackgroundStatusEvent::MetadataLoaded => self.fill_loaded_metadata(),
+            BackgroundStatusEvent::OutputDeviceRestored => {
+                self.main_state.is_output_available = true;
+            }
+            BackgroundStatusEvent::ResumePositionChanged {
+                file_path,
+                position,
+            } => {
+                if let Some(music) = self.find_music_mut(file_path) {
+                    music.resume_position = *position;
+                }
+            }
+            BackgroundStatusEvent::PlayCountChanged {
+                file_path,
+                play_count,
+            } => {
+                if let Some(music) = self.find_music_mut(file_path) {
+                    music.play_count = *play_count;
+                }
+            }
+        }
+    }
+
+    /// 음악 폴더에 있는 음악 파일 목록 (재생 목록과 상관없이 폴더를 다시 읽는다.)
+    pub fn library(&self) -> anyhow::Result<Vec<PathBuf>> {
+        let file_info_list = file::read_file_list(&self.config_data.directory_path)?;
+
+        Ok(file_info_list
+            .into_iter()
+            .filter(|file_info| file_info.is_music_file())
+            .map(|file_info| file_info.filepath)
+            .collect())
+    }
+
+    /// 재생 목록에서 index번째 곡을 뺀다. 범위를 벗어나면 false를 반환한다.
+    pub fn remove_music(&mut self, index: usize) -> bool {
+        if index >= self.main_state.music_list.list.len() {
+            return false;
+        }
+
+        // 재생 중인 곡을 지우면 백그라운드 루프가 재생을 멈춘다.
+        self.main_state.music_list.list.remove(index);
+        self.send_music_list_to_background();
+
+        true
+    }
+
+    pub fn clear_music_list(&mut self) {
+        self.main_state.music_list.list.clear();
+        self.send_music_list_to_background();
+    }
+
+    /// 설정을 저장하고, 백그라운드 루프가 세션을 저장할 때까지 잠시 기다린다.
+    pub fn shutdown(&self) {
+        self.save_config();
+
+        let (reply_sender, reply_receiver) = mpsc::channel();
+
+        if let Err(error) = self
+            .background_event_sender
+            .send(BackgroundLoopEvent::Shutdown(reply_sender))
+        {
+            log::error!("Failed to send event: {:?}", error);
+        } else if let Err(error) = reply_receiver.recv_timeout(SHUTDOWN_TIMEOUT) {
+            log::error!("Failed to save session: {:?}", error);
+        }
+    }
+
+    /// 설정 파일이 있으면 현재 설정을 저장한다.
+    /// PlayerOptions로 덮어쓴 셔플, 볼륨은 직접 바꾸기 전까지 설정 파일의 원래 값으로 저장한다.
+    pub fn save_config(&self) {
+        let mut config_data = self.config_data.clone();
+        self.config_overrides.restore(&mut config_data);
+
+        if let Err(err) = config_data.update_config_if_exists(self.config_path.clone()) {
+            log::error!("Failed to update config: {:?}", err);
+        }
+    }
+
+    /// 이 재생 목록과 같은 순서의 목록을 백그라운드 루프에 전달한다.
+    /// (DirectPlayMusic 등 인덱스로 주고받는 이벤트가 같은 곡을 가리키도록)
+    pub fn send_music_list_to_background(&self) {
+        if let Err(error) =
+            self.background_event_sender
+                .send(BackgroundLoopEvent::MusicListChanged(
+                    self.main_state.music_list.clone(),
+                ))
+        {
+            log::error!("Failed to send event: {:?}", error);
+        }
+    }
+
+    /// 곡의 태그를 백그라운드 스레드에서 읽는다. (큰 라이브러리에서 화면이 멈추지 않도록)
+    /// 읽은 태그는 loaded_metadata에 모아두고 MetadataLoaded로 알린다.
+    fn load_metadata(&self, music_list: &[Music]) {
+        let file_paths = music_list
+            .iter()
+            .map(|music| music.file_path.clone())
+            .collect::<Vec<_>>();
+
+        if file_paths.is_empty() {
+            return;
+        }
+
+        let background_state = self.background_state.clone();
+
+        std::thread::spawn(move || {
+            let mut last_publish = Instant::now();
+
+            for file_path in file_paths {
+                let metadata = metadata::read_metadata(&file_path).unwrap_or_default();
+                background_state
+                    .loaded_metadata
+                    .lock()
+                    .unwrap()
+                    .insert(file_path, metadata);
+
+                if last_publish.elapsed() >= METADATA_PUBLISH_INTERVAL {
+                    background_state.publish(BackgroundStatusEvent::MetadataLoaded);
+                    last_publish = Instant::now();
+                }
+            }
+
+            background_state.publish(BackgroundStatusEvent::MetadataLoaded);
+        });
+    }
+
+    /// 백그라운드에서 읽은 태그를 재생 목록에 채운다.
+    fn fill_loaded_metadata(&mut self) {
+        let mut loaded_metadata =
+            std::mem::take(&mut *self.background_state.loaded_metadata.lock().unwrap());
+
+        if loaded_metadata.is_empty() {
+            return;
+        }
+
+        for music in self.main_state.music_list.list.iter_mut() {
+            if let Some(metadata) = loaded_metadata.remove(&music.file_path) {
+                music.metadata = metadata;
+            }
+        }
+
+        if self.config_data.sort_order.uses_metadata() {
+            sort::sort_music_list(&mut self.main_state.music_list, self.config_data.sort_order);
+            self.send_music_list_to_background();
+        }
+    }
+
+    fn find_music_mut(&mut self, file_path: &Path) -> Option<&mut Music> {
+        self.main_state
+            .music_list
+            .list
+            .iter_mut()
+            .find(|music| music.file_path == file_path)
+    }
+}
+
+/// 파일 목록 중 음악 파일만 이어서 재생할 위치, 재생 횟수와 함께 읽는다.
+/// 태그는 파일마다 열어봐야 해서 느리므로 load_metadata로 따로 읽는다.
+fn read_music_list(file_info_list: &[FileInfo], app_data_path: &Path) -> Vec<Music> {
+    let resume_positions = resume_position::read_resume_positions_if_exists(
+        resume_position::get_resume_positions_path(app_data_path),
+    )
+    .unwrap_or_default();
+    let play_counts =
+        play_count::read_play_counts_if_exists(play_count::get_play_counts_path(app_data_path))
+            .unwrap_or_default();
+
+    file_info_list
+        .iter()
+        .filter(|x| x.is_music_file())
+        .map(|x| Music {
+            title: x.filename.clone(),
+            file_path: x.filepath.clone(),
+            error: None,
+            resume_position: resume_positions.get(&x.filepath),
+            metadata: Default::default(),
+            added_at: x.added_at,
+            play_count: play_counts.get(&x.filepath),
+        })
+        .collect()
+}
@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// 긴 곡(오디오북, 팟캐스트 등)의 곡별 재생 위치
+/// 다른 곡으로 넘어갔다가 돌아왔을 때 처음부터 다시 듣지 않도록 한다.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ResumePositions {
+    #[serde(default)]
+    pub positions: HashMap<PathBuf, u64>, // 곡 경로 -> 재생 위치 (밀리초)
+}
+
+impl ResumePositions {
+    pub fn get(&self, file_path: &Path) -> Option<Duration> {
+        self.positions
+            .get(file_path)
+            .copied()
+            .map(Duration::from_millis)
+    }
+
+    /// 재생 위치를 기록하거나(None이면) 지운다. 값이 바뀌었으면 true를 반환한다.
+    pub fn set(&mut self, file_path: PathBuf, position: Option<Duration>) -> bool {
+        match position {
+            Some(position) => {
+                let position_millis = position.as_millis() as u64;
+
+                self.positions.insert(file_path, position_millis) != Some(position_millis)
+            }
+            None => self.positions.remove(&file_path).is_some(),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let positions_str = serde_json::to_string(self)?;
+
+        fs::write(path, positions_str)?;
+
+        Ok(())
+    }
+}
+
+pub fn get_resume_positions_path(app_data_path: &Path) -> PathBuf {
+    app_data_path.join("positions.json")
+}
+
+pub fn read_resume_positions_if_exists(path: PathBuf) -> anyhow::Result<ResumePositions> {
+    let positions_str = fs::read_to_string(path)?;
+
+    let positions: ResumePositions = serde_json::from_str(&positions_str)?;
+
+    Ok(positions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_only_actual_changes() {
+        let mut positions = ResumePositions::default();
+        let file_path = PathBuf::from("/music/book.mp3");
+
+        assert!(positions.set(file_path.clone(), Some(Duration::from_secs(5))));
+        assert!(!positions.set(file_path.clone(), Some(Duration::from_secs(5))));
+        assert_eq!(Some(Duration::from_secs(5)), positions.get(&file_path));
+
+        assert!(positions.set(file_path.clone(), None));
+        assert!(!positions.set(file_path.clone(), None));
+        assert_eq!(None, positions.get(&file_path));
+    }
+}
@@ -0,0 +1,253 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::state::MusicList;
+
+/// 마지막 재생 세션
+/// 앱 종료 시(그리고 주기적으로) 저장해두었다가 다음 실행 때 이어서 재생한다.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Session {
+    #[serde(default)]
+    pub queue: Vec<PathBuf>, // 재생 목록 순서
+    #[serde(default)]
+    pub current_file_path: Option<PathBuf>, // 재생 중이던 곡
+    #[serde(default)]
+    pub position_millis: u64, // 재생 중이던 위치
+    #[serde(default)]
+    pub is_paused: bool,
+    #[serde(default)]
+    pub shuffle_order: Vec<PathBuf>, // 랜덤 모드의 재생 순서
+}
+
+/// 세션을 복원할 때 백그라운드 루프가 이어서 재생할 지점
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResumePoint {
+    pub music_index: usize,
+    pub position: Duration,
+    pub is_paused: bool,
+    pub random_indices: Option<Vec<usize>>, // 저장된 랜덤 순서가 현재 목록과 맞지 않으면 None
+}
+
+impl Session {
+    pub fn position(&self) -> Duration {
+        Duration::from_millis(self.position_millis)
+    }
+
+    /// 설정의 정렬 기준으로 정렬된 재생 목록을 저장된 순서대로 되돌린다.
+    /// 세션에 없던 곡(새로 추가된 파일 등)은 정렬된 목록에서 바로 앞에 있던 곡 뒤에 놓는다.
+    pub fn restore_queue_order(&self, music_list: &mut MusicList) {
+        if self.queue.is_empty() {
+            return;
+        }
+
+        let queue_positions = self
+            .queue
+            .iter()
+            .enumerate()
+            .map(|(position, file_path)| (file_path, position))
+            .collect::<HashMap<_, _>>();
+
+        // (앞에 있는 세션 곡의 위치, 세션에 없는 곡이면 정렬된 순서)
+        let mut previous_position = None;
+        let mut keyed_list = std::mem::take(&mut music_list.list)
+            .into_iter()
+            .enumerate()
+            .map(|(index, music)| {
+                let key = match queue_positions.get(&music.file_path) {
+                    Some(&position) => {
+                        previous_position = Some(position);
+                        (Some(position), 0)
+                    }
+                    None => (previous_position, index + 1),
+                };
+
+                (key, music)
+            })
+            .collect::<Vec<_>>();
+
+        keyed_list.sort_by_key(|(key, _)| *key);
+
+        music_list.list = keyed_list.into_iter().map(|(_, music)| music).collect();
+    }
+
+    /// 현재 목록에서 이어서 재생할 지점을 찾는다. (재생 중이던 곡이 사라졌으면 None)
+    pub fn resume_point(&self, music_list: &MusicList, start_paused: bool) -> Option<ResumePoint> {
+        let current_file_path = self.current_file_path.as_ref()?;
+
+        let music_index = music_list
+            .list
+            .iter()
+            .position(|music| &music.file_path == current_file_path)?;
+
+        let music_indices = music_list
+            .list
+            .iter()
+            .enumerate()
+            .map(|(index, music)| (&music.file_path, index))
+            .collect::<HashMap<_, _>>();
+
+        // 목록의 곡을 한 번씩 모두 담고 있을 때만 셔플 순서를 이어간다.
+        let random_indices = self
+            .shuffle_order
+            .iter()
+            .map(|file_path| music_indices.get(file_path).copied())
+            .collect::<Option<Vec<_>>>()
+            .filter(|indices| {
+                indices.len() == music_list.list.len()
+                    && indices.iter().collect::<HashSet<_>>().len() == indices.len()
+            });
+
+        Some(ResumePoint {
+            music_index,
+            position: self.position(),
+            is_paused: self.is_paused || start_paused,
+            random_indices,
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let session_str = serde_json::to_string(self)?;
+
+        fs::write(path, session_str)?;
+
+        Ok(())
+    }
+}
+
+pub fn get_session_path(app_data_path: &Path) -> PathBuf {
+    app_data_path.join("session.json")
+}
+
+pub fn read_session_if_exists(path: PathBuf) -> anyhow::Result<Session> {
+    let session_str = fs::read_to_string(path)?;
+
+    let session: Session = serde_json::from_str(&session_str)?;
+
+    Ok(session)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::Music;
+
+    fn music_list(names: &[&str]) -> MusicList {
+        MusicList {
+            list: names
+                .iter()
+                .map(|name| Music {
+                    title: name.to_string(),
+                    file_path: PathBuf::from(format!("/music/{name}")),
+                    ..Default::default()
+                })
+                .collect(),
+        }
+    }
+
+    fn paths(names: &[&str]) -> Vec<PathBuf> {
+        names
+            .iter()
+            .map(|name| PathBuf::from(format!("/music/{name}")))
+            .collect()
+    }
+
+    fn titles(music_list: &MusicList) -> Vec<&str> {
+        music_list
+            .list
+            .iter()
+            .map(|music| music.title.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn restores_queue_order_and_keeps_new_music_in_sorted_position() {
+        let session = Session {
+            queue: paths(&["c", "a", "gone"]),
+            ..Default::default()
+        };
+        // 정렬된 목록에서 b는 a 뒤에, d는 c 뒤에, 0은 맨 앞에 있다.
+        let mut music_list = music_list(&["0", "a", "b", "c", "d"]);
+
+        session.restore_queue_order(&mut music_list);
+
+        assert_eq!(vec!["0", "c", "d", "a", "b"], titles(&music_list));
+    }
+
+    #[test]
+    fn resumes_at_saved_music() {
+        let session = Session {
+            current_file_path: Some(PathBuf::from("/music/b")),
+            position_millis: 42_000,
+            shuffle_order: paths(&["c", "a", "b"]),
+            ..Default::default()
+        };
+
+        let resume_point = session
+            .resume_point(&music_list(&["a", "b", "c"]), false)
+            .unwrap();
+
+        assert_eq!(
+            ResumePoint {
+                music_index: 1,
+                position: Duration::from_secs(42),
+                is_paused: false,
+                random_indices: Some(vec![2, 0, 1]),
+            },
+            resume_point
+        );
+    }
+
+    #[test]
+    fn start_paused_overrides_saved_pause_state() {
+        let session = Session {
+            current_file_path: Some(PathBuf::from("/music/a")),
+            ..Default::default()
+        };
+
+        let resume_point = session.resume_point(&music_list(&["a"]), true).unwrap();
+
+        assert!(resume_point.is_paused);
+    }
+
+    #[test]
+    fn ignores_shuffle_order_that_no_longer_matches() {
+        let session = Session {
+            current_file_path: Some(PathBuf::from("/music/a")),
+            shuffle_order: paths(&["b", "a"]),
+            ..Default::default()
+        };
+
+        let resume_point = session
+            .resume_point(&music_list(&["a", "b", "c"]), false)
+            .unwrap();
+
+        assert_eq!(None, resume_point.random_indices);
+    }
+
+    #[test]
+    fn ignores_shuffle_order_with_duplicates() {
+        let session = Session {
+            current_file_path: Some(PathBuf::from("/music/a")),
+            shuffle_order: paths(&["a", "b", "a"]),
+            ..Default::default()
+        };
+
+        let resume_point = session
+            .resume_point(&music_list(&["a", "b", "c"]), false)
+            .unwrap();
+
+        assert_eq!(None, resume_point.random_indices);
+    }
+
+    #[test]
+    fn does_not_resume_removed_music() {
+        let session = Session {
+            current_file_path: Some(PathBuf::from("/music/gone")),
+            ..Default::default()
+        };
+
+        assert_eq!(None, session.resume_point(&music_list(&["a"]), false));
+    }
+}
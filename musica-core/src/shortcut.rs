@@ -0,0 +1,307 @@
+/// 단축키로 이동하는 재생 위치 (밀리초)
+pub const SEEK_STEP_MILLIS: i64 = 5_000;
+
+/// 단축키로 조절하는 볼륨 단위
+pub const VOLUME_STEP: f32 = 0.05;
+
+/// 키보드 단축키로 실행할 수 있는 동작
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShortcutAction {
+    PlayPause,
+    Next,
+    Previous,
+    SeekForward,
+    SeekBackward,
+    VolumeUp,
+    VolumeDown,
+    Mute,
+    ToggleShuffle,
+    OpenSettings,
+    ShowShortcuts,
+}
+
+impl ShortcutAction {
+    pub const ALL: [ShortcutAction; 11] = [
+        ShortcutAction::PlayPause,
+        ShortcutAction::Next,
+        ShortcutAction::Previous,
+        ShortcutAction::SeekForward,
+        ShortcutAction::SeekBackward,
+        ShortcutAction::VolumeUp,
+        ShortcutAction::VolumeDown,
+        ShortcutAction::Mute,
+        ShortcutAction::ToggleShuffle,
+        ShortcutAction::OpenSettings,
+        ShortcutAction::ShowShortcuts,
+    ];
+
+    pub fn description(self) -> &'static str {
+        match self {
+            ShortcutAction::PlayPause => "Play / Pause",
+            ShortcutAction::Next => "Next",
+            ShortcutAction::Previous => "Previous",
+            ShortcutAction::SeekForward => "Seek +5s",
+            ShortcutAction::SeekBackward => "Seek -5s",
+            ShortcutAction::VolumeUp => "Volume Up",
+            ShortcutAction::VolumeDown => "Volume Down",
+            ShortcutAction::Mute => "Mute",
+            ShortcutAction::ToggleShuffle => "Toggle Random Mode",
+            ShortcutAction::OpenSettings => "Open Setting",
+            ShortcutAction::ShowShortcuts => "Show Shortcuts",
+        }
+    }
+}
+
+/// 동작별 키 바인딩 ("Space", "Ctrl+,", "Shift+Right" 형식)
+/// 설정 파일에서 원하는 동작만 덮어쓸 수 있다.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct KeyBindings {
+    pub play_pause: String,
+    pub next: String,
+    pub previous: String,
+    pub seek_forward: String,
+    pub seek_backward: String,
+    pub volume_up: String,
+    pub volume_down: String,
+    pub mute: String,
+    pub toggle_shuffle: String,
+    pub open_settings: String,
+    pub show_shortcuts: String,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            play_pause: "Space".into(),
+            next: "N".into(),
+            previous: "P".into(),
+            seek_forward: "Right".into(),
+            seek_backward: "Left".into(),
+            volume_up: "Up".into(),
+            volume_down: "Down".into(),
+            mute: "M".into(),
+            toggle_shuffle: "S".into(),
+            open_settings: "Ctrl+,".into(),
+            show_shortcuts: "?".into(),
+        }
+    }
+}
+
+impl KeyBindings {
+    pub fn binding(&self, action: ShortcutAction) -> &str {
+        match action {
+            ShortcutAction::PlayPause => &self.play_pause,
+            ShortcutAction::Next => &self.next,
+            ShortcutAction::Previous => &self.previous,
+            ShortcutAction::SeekForward => &self.seek_forward,
+            ShortcutAction::SeekBackward => &self.seek_backward,
+            ShortcutAction::VolumeUp => &self.volume_up,
+            ShortcutAction::VolumeDown => &self.volume_down,
+            ShortcutAction::Mute => &self.mute,
+            ShortcutAction::ToggleShuffle => &self.toggle_shuffle,
+            ShortcutAction::OpenSettings => &self.open_settings,
+            ShortcutAction::ShowShortcuts => &self.show_shortcuts,
+        }
+    }
+
+    /// 눌린 키에 바인딩된 동작을 찾는다.
+    pub fn action_for(&self, key_press: &KeyPress) -> Option<ShortcutAction> {
+        ShortcutAction::ALL.into_iter().find(|&action| {
+            KeyCombination::parse(self.binding(action))
+                .is_some_and(|combination| combination.matches(key_press))
+        })
+    }
+}
+
+/// 눌린 키 (UI 라이브러리와 무관한 형태)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyPress {
+    pub key: String, // 소문자 키 이름 ("space", "arrowleft", "n", "?")
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+    pub logo: bool,
+}
+
+/// 바인딩 문자열을 해석한 키 조합
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct KeyCombination {
+    key: String,
+    ctrl: bool,
+    shift: bool,
+    alt: bool,
+    logo: bool,
+}
+
+impl KeyCombination {
+    fn parse(binding: &str) -> Option<Self> {
+        let binding = binding.trim();
+
+        // "Ctrl++" 처럼 '+' 키 자체를 바인딩한 경우
+        let (modifiers, key) = match binding.strip_suffix('+') {
+            Some(rest) if rest.is_empty() || rest.ends_with('+') => {
+                (rest.strip_suffix('+').unwrap_or_default(), "+")
+            }
+            _ => match binding.rsplit_once('+') {
+                Some((modifiers, key)) => (modifiers, key),
+                None => ("", binding),
+            },
+        };
+
+        let key = normalize_key_name(key.trim())?;
+
+        let mut combination = Self {
+            key,
+            ctrl: false,
+            shift: false,
+            alt: false,
+            logo: false,
+        };
+
+        for modifier in modifiers.split('+').filter(|modifier| !modifier.is_empty()) {
+            match modifier.trim().to_lowercase().as_str() {
+                "ctrl" | "control" => combination.ctrl = true,
+                "shift" => combination.shift = true,
+                "alt" | "option" => combination.alt = true,
+                "super" | "logo" | "cmd" | "command" | "meta" => combination.logo = true,
+                _ => return None,
+            }
+        }
+
+        Some(combination)
+    }
+
+    fn matches(&self, key_press: &KeyPress) -> bool {
+        // '?' 처럼 Shift를 눌러야만 입력되는 문자는 Shift 여부를 따지지 않는다.
+        let ignores_shift = !self.shift
+            && self.key.chars().count() == 1
+            && !self.key.chars().all(char::is_alphanumeric);
+
+        self.key == key_press.key
+            && self.ctrl == key_press.ctrl
+            && (ignores_shift || self.shift == key_press.shift)
+            && self.alt == key_press.alt
+            && self.logo == key_press.logo
+    }
+}
+
+/// 키 이름의 별칭을 정리하고 소문자로 맞춘다.
+fn normalize_key_name(key: &str) -> Option<String> {
+    if key.is_empty() {
+        return None;
+    }
+
+    let key = key.to_lowercase();
+
+    let key = match key.as_str() {
+        "left" => "arrowleft".into(),
+        "right" => "arrowright".into(),
+        "up" => "arrowup".into(),
+        "down" => "arrowdown".into(),
+        "esc" => "escape".into(),
+        "return" => "enter".into(),
+        "del" => "delete".into(),
+        _ => key,
+    };
+
+    Some(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(key: &str) -> KeyPress {
+        KeyPress {
+            key: key.into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parses_modifiers_and_aliases() {
+        assert_eq!(
+            Some(KeyCombination {
+                key: "arrowright".into(),
+                ctrl: true,
+                shift: true,
+                alt: false,
+                logo: false,
+            }),
+            KeyCombination::parse("Ctrl+Shift+Right")
+        );
+        assert_eq!(
+            Some(KeyCombination {
+                key: "+".into(),
+                ctrl: true,
+                shift: false,
+                alt: false,
+                logo: false,
+            }),
+            KeyCombination::parse("Ctrl++")
+        );
+        assert_eq!(None, KeyCombination::parse("Hyper+A"));
+        assert_eq!(None, KeyCombination::parse(""));
+    }
+
+    #[test]
+    fn finds_default_bindings() {
+        let key_bindings = KeyBindings::default();
+
+        assert_eq!(
+            Some(ShortcutAction::PlayPause),
+            key_bindings.action_for(&key("space"))
+        );
+        assert_eq!(
+            Some(ShortcutAction::SeekBackward),
+            key_bindings.action_for(&key("arrowleft"))
+        );
+        assert_eq!(
+            Some(ShortcutAction::OpenSettings),
+            key_bindings.action_for(&KeyPress {
+                ctrl: true,
+                ..key(",")
+            })
+        );
+        assert_eq!(None, key_bindings.action_for(&key(",")));
+    }
+
+    #[test]
+    fn shift_is_ignored_only_for_symbols() {
+        let key_bindings = KeyBindings::default();
+
+        assert_eq!(
+            Some(ShortcutAction::ShowShortcuts),
+            key_bindings.action_for(&KeyPress {
+                shift: true,
+                ..key("?")
+            })
+        );
+        assert_eq!(
+            None,
+            key_bindings.action_for(&KeyPress {
+                shift: true,
+                ..key("n")
+            })
+        );
+    }
+
+    #[test]
+    fn overrides_only_configured_bindings() {
+        let key_bindings: KeyBindings = serde_json::from_str(r#"{"next":"Ctrl+Right"}"#).unwrap();
+
+        assert_eq!(None, key_bindings.action_for(&key("n")));
+        assert_eq!(
+            Some(ShortcutAction::Next),
+            key_bindings.action_for(&KeyPress {
+                ctrl: true,
+                ..key("arrowright")
+            })
+        );
+        assert_eq!(
+            Some(ShortcutAction::PlayPause),
+            key_bindings.action_for(&key("space"))
+        );
+    }
+}
@@ -0,0 +1,254 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::state::{Music, MusicList};
+
+/// 재생 목록 정렬 기준
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    #[default]
+    FileName, // 파일 이름 (숫자는 크기 순: "Track 2" < "Track 10")
+    Title,
+    Artist,
+    Album, // 앨범 → 트랙 번호
+    Duration,
+    DateAdded, // 최근에 추가한 곡부터
+    PlayCount, // 많이 들은 곡부터
+}
+
+impl SortOrder {
+    pub const ALL: [SortOrder; 7] = [
+        SortOrder::FileName,
+        SortOrder::Title,
+        SortOrder::Artist,
+        SortOrder::Album,
+        SortOrder::Duration,
+        SortOrder::DateAdded,
+        SortOrder::PlayCount,
+    ];
+
+    /// 태그 정보로 정렬하는지 여부 (태그를 나중에 읽으면 다시 정렬해야 함)
+    pub fn uses_metadata(self) -> bool {
+        matches!(
+            self,
+            SortOrder::Title | SortOrder::Artist | SortOrder::Album | SortOrder::Duration
+        )
+    }
+}
+
+impl fmt::Display for SortOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            SortOrder::FileName => "File Name",
+            SortOrder::Title => "Title",
+            SortOrder::Artist => "Artist",
+            SortOrder::Album => "Album",
+            SortOrder::Duration => "Duration",
+            SortOrder::DateAdded => "Date Added",
+            SortOrder::PlayCount => "Play Count",
+        };
+
+        f.write_str(label)
+    }
+}
+
+/// 재생 목록을 정렬한다. 기준 값이 같거나 없는 곡은 파일 이름 순으로 뒤에 놓는다.
+pub fn sort_music_list(music_list: &mut MusicList, sort_order: SortOrder) {
+    music_list
+        .list
+        .sort_by(|a, b| compare_by(a, b, sort_order).then_with(|| natural_cmp(&a.title, &b.title)));
+}
+
+fn compare_by(a: &Music, b: &Music, sort_order: SortOrder) -> Ordering {
+    match sort_order {
+        SortOrder::FileName => Ordering::Equal,
+        SortOrder::Title => compare_optional(
+            a.metadata.title.as_deref().or(Some(&a.title)),
+            b.metadata.title.as_deref().or(Some(&b.title)),
+            natural_cmp,
+        ),
+        SortOrder::Artist => compare_optional(
+            a.metadata.artist.as_deref(),
+            b.metadata.artist.as_deref(),
+            natural_cmp,
+        ),
+        SortOrder::Album => compare_optional(
+            a.metadata.album.as_deref(),
+            b.metadata.album.as_deref(),
+            natural_cmp,
+        )
+        .then_with(|| {
+            compare_optional(a.metadata.track_number, b.metadata.track_number, |a, b| {
+                a.cmp(&b)
+            })
+        }),
+        SortOrder::Duration => {
+            compare_optional(a.metadata.duration, b.metadata.duration, |a, b| a.cmp(&b))
+        }
+        SortOrder::DateAdded => compare_optional(a.added_at, b.added_at, |a, b| b.cmp(&a)),
+        SortOrder::PlayCount => b.play_count.cmp(&a.play_count),
+    }
+}
+
+/// 값이 없는 쪽을 뒤로 보낸다.
+fn compare_optional<T>(a: Option<T>, b: Option<T>, compare: impl Fn(T, T) -> Ordering) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => compare(a, b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// 숫자 부분은 크기로, 나머지는 대소문자 구분 없이 비교한다. ("Track 2" < "Track 10")
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let mut a_chars = a.chars().peekable();
+    let mut b_chars = b.chars().peekable();
+
+    loop {
+        match (a_chars.peek().copied(), b_chars.peek().copied()) {
+            (None, None) => return a.cmp(b),
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(a_char), Some(b_char)) if a_char.is_ascii_digit() && b_char.is_ascii_digit() => {
+                let a_number = take_number(&mut a_chars);
+                let b_number = take_number(&mut b_chars);
+
+                let a_digits = a_number.trim_start_matches('0');
+                let b_digits = b_number.trim_start_matches('0');
+
+                let ordering = a_digits
+                    .len()
+                    .cmp(&b_digits.len())
+                    .then_with(|| a_digits.cmp(b_digits));
+
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            (Some(a_char), Some(b_char)) => {
+                let ordering = a_char.to_lowercase().cmp(b_char.to_lowercase());
+
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+
+                a_chars.next();
+                b_chars.next();
+            }
+        }
+    }
+}
+
+fn take_number(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> String {
+    let mut number = String::new();
+
+    while let Some(c) = chars.next_if(char::is_ascii_digit) {
+        number.push(c);
+    }
+
+    number
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::time::{Duration, SystemTime};
+
+    use super::*;
+    use crate::metadata::MusicMetadata;
+
+    fn music(title: &str) -> Music {
+        Music {
+            title: title.into(),
+            file_path: PathBuf::from(format!("/music/{title}")),
+            ..Default::default()
+        }
+    }
+
+    fn titles(music_list: &MusicList) -> Vec<&str> {
+        music_list
+            .list
+            .iter()
+            .map(|music| music.title.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn compares_numbers_by_value() {
+        let mut names = vec![
+            "Track 10.mp3",
+            "track 2.mp3",
+            "Track 1.mp3",
+            "Track 02b.mp3",
+        ];
+
+        names.sort_by(|a, b| natural_cmp(a, b));
+
+        assert_eq!(
+            vec![
+                "Track 1.mp3",
+                "track 2.mp3",
+                "Track 02b.mp3",
+                "Track 10.mp3"
+            ],
+            names
+        );
+    }
+
+    #[test]
+    fn sorts_by_album_and_track_number_with_missing_tags_last() {
+        let album = |title: &str, album: Option<&str>, track_number: Option<u32>| Music {
+            metadata: MusicMetadata {
+                album: album.map(str::to_string),
+                track_number,
+                ..Default::default()
+            },
+            ..music(title)
+        };
+
+        let mut music_list = MusicList {
+            list: vec![
+                album("untagged", None, None),
+                album("b2", Some("B"), Some(2)),
+                album("a10", Some("A"), Some(10)),
+                album("b1", Some("B"), Some(1)),
+                album("a9", Some("A"), Some(9)),
+            ],
+        };
+
+        sort_music_list(&mut music_list, SortOrder::Album);
+
+        assert_eq!(
+            vec!["a9", "a10", "b1", "b2", "untagged"],
+            titles(&music_list)
+        );
+    }
+
+    #[test]
+    fn sorts_recent_and_most_played_first() {
+        let now = SystemTime::now();
+        let mut music_list = MusicList {
+            list: vec![
+                Music {
+                    added_at: Some(now - Duration::from_secs(60)),
+                    play_count: 5,
+                    ..music("old")
+                },
+                Music {
+                    added_at: Some(now),
+                    play_count: 1,
+                    ..music("new")
+                },
+                music("unknown"),
+            ],
+        };
+
+        sort_music_list(&mut music_list, SortOrder::DateAdded);
+        assert_eq!(vec!["new", "old", "unknown"], titles(&music_list));
+
+        sort_music_list(&mut music_list, SortOrder::PlayCount);
+        assert_eq!(vec!["old", "new", "unknown"], titles(&music_list));
+    }
+}
@@ -0,0 +1,48 @@
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use crate::metadata::MusicMetadata;
+
+#[derive(Debug, Clone)]
+pub struct MainState {
+    pub title: String,
+    pub music_list: MusicList,
+    pub on_play: bool,
+    pub is_stopped: bool,
+    pub stop_after_current: bool, // 현재 곡이 끝나면 멈출지 여부
+    pub is_output_available: bool,
+    pub is_muted: bool,             // 음소거 중이면 설정된 볼륨 대신 0으로 재생
+    pub current_music_index: usize, // 재생 중인 곡의 실제 인덱스
+    pub position: Duration,         // 현재 곡의 재생 위치
+    pub duration: Option<Duration>, // 현재 곡의 전체 길이
+    pub album_art_path: Option<PathBuf>, // 현재 곡의 앨범 커버 썸네일
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Music {
+    pub title: String,
+    pub file_path: PathBuf,
+    pub error: Option<String>, // 재생할 수 없는 곡이면 그 이유 (손상된 파일, 지원하지 않는 코덱 등)
+    pub resume_position: Option<Duration>, // 긴 곡을 이어서 재생할 위치
+    pub metadata: MusicMetadata, // 파일에서 읽은 태그 정보
+    pub added_at: Option<SystemTime>, // 파일을 라이브러리에 추가한 시각
+    pub play_count: u32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MusicList {
+    pub list: Vec<Music>,
+}
+
+impl MusicList {
+    pub fn is_not_empty(&self) -> bool {
+        !self.list.is_empty()
+    }
+}
+
+/// 재생 시간을 m:ss 형식으로 표시 (화면, TUI, ctl 상태 출력에서 함께 사용)
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
@@ -0,0 +1,136 @@
+use std::path::PathBuf;
+use std::sync::{OnceLock, mpsc};
+use std::time::{Duration, Instant};
+
+use musica_core::backend::output::AudioOutput;
+use musica_core::backend::output::fake::FakeOutput;
+use musica_core::backend::state::BackgroundStatusEvent;
+use musica_core::player::{Player, PlayerOptions};
+
+/// 상태 이벤트를 기다리는 최대 시간
+const EVENT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 백그라운드 루프가 여는 출력 (fn 포인터로 넘기므로 static에 둔다)
+fn fake_output() -> FakeOutput {
+    static OUTPUT: OnceLock<FakeOutput> = OnceLock::new();
+
+    OUTPUT.get_or_init(FakeOutput::new).clone()
+}
+
+fn open_fake_output(volume: f32) -> anyhow::Result<Box<dyn AudioOutput>> {
+    let mut output = fake_output();
+    output.set_volume(volume);
+
+    Ok(Box::new(output))
+}
+
+/// 조건을 만족할 때까지 기다린다.
+fn wait_until(description: &str, condition: impl Fn() -> bool) {
+    let started = Instant::now();
+
+    while !condition() {
+        assert!(
+            started.elapsed() < EVENT_TIMEOUT,
+            "{description} did not happen"
+        );
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
+/// 조건에 맞는 이벤트가 올 때까지 받은 이벤트를 player에 넘긴다.
+fn wait_for(
+    player: &mut Player,
+    events: &mpsc::Receiver<BackgroundStatusEvent>,
+    expected: BackgroundStatusEvent,
+) {
+    loop {
+        let event = events
+            .recv_timeout(EVENT_TIMEOUT)
+            .unwrap_or_else(|_| panic!("{expected:?} was not published"));
+        player.handle_status(&event);
+
+        if event == expected {
+            return;
+        }
+    }
+}
+
+#[test]
+fn plays_music_and_streams_status_without_frontend() {
+    let directory = std::env::temp_dir().join(format!("musica-core-player-{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+
+    let music_paths = ["a.mp3", "b.mp3", "c.mp3"]
+        .iter()
+        .map(|name| directory.join(name))
+        .collect::<Vec<PathBuf>>();
+    for music_path in &music_paths {
+        std::fs::write(music_path, b"not really audio").unwrap();
+    }
+
+    let mut player = Player::new(PlayerOptions {
+        config_path: directory.join("config.json"),
+        paths: music_paths.clone(),
+        is_random: Some(false),
+        open_device: open_fake_output,
+        // 세션, 재생 위치, 재생 횟수를 실제 앱 데이터 폴더 대신 임시 폴더에 저장한다.
+        app_data_path: directory.clone(),
+        ..Default::default()
+    });
+
+    let (event_sender, events) = mpsc::channel();
+    let status_stream = player.status_stream();
+    std::thread::spawn(move || {
+        for event in status_stream {
+            if event_sender.send(event).is_err() {
+                return;
+            }
+        }
+    });
+
+    // 첫 곡을 출력에 넘겨 재생을 시작한다.
+    wait_until("playing the first music", || {
+        fake_output().current() == Some(music_paths[0].clone())
+    });
+    assert!(!fake_output().is_paused());
+
+    player.next();
+    wait_for(&mut player, &events, BackgroundStatusEvent::MusicChanged(1));
+    assert_eq!(Some(music_paths[1].clone()), fake_output().current());
+    assert_eq!(1, player.main_state.current_music_index);
+
+    player.toggle_pause();
+    wait_for(
+        &mut player,
+        &events,
+        BackgroundStatusEvent::PauseChanged(true),
+    );
+    assert!(fake_output().is_paused());
+    assert!(!player.main_state.on_play);
+
+    player.set_volume(0.25);
+    wait_for(
+        &mut player,
+        &events,
+        BackgroundStatusEvent::VolumeChanged(0.25),
+    );
+    assert_eq!(0.25, fake_output().volume());
+
+    player.toggle_pause();
+    wait_for(
+        &mut player,
+        &events,
+        BackgroundStatusEvent::PauseChanged(false),
+    );
+    assert!(!fake_output().is_paused());
+    assert_eq!(
+        vec![music_paths[0].clone(), music_paths[1].clone()],
+        fake_output().state().appended
+    );
+
+    // 종료하면서 세션을 지정한 폴더에 남긴다.
+    player.shutdown();
+    assert!(directory.join("session.json").exists());
+
+    std::fs::remove_dir_all(&directory).unwrap();
+}
@@ -0,0 +1,261 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use musica_core::{config, metadata};
+
+/// 캐시에 저장하는 썸네일의 최대 크기 (고해상도 화면을 고려해 표시 크기의 2배)
+const THUMBNAIL_SIZE: u32 = 128;
+
+/// 곡 파일에 커버가 없을 때 같은 폴더에서 찾아볼 이미지 파일 이름
+const FOLDER_IMAGE_NAMES: [&str; 6] = [
+    "cover.jpg",
+    "cover.png",
+    "folder.jpg",
+    "folder.png",
+    "front.jpg",
+    "front.png",
+];
+
+pub fn get_album_art_cache_path() -> PathBuf {
+    let app_data_path = config::get_app_data_path();
+
+    app_data_path.join("album_art")
+}
+
+/// 곡의 앨범 커버 썸네일 경로를 반환한다. (커버가 없으면 None)
+/// 처음 읽을 때 썸네일을 만들어 캐시 폴더에 저장하고, 이후에는 캐시를 그대로 사용한다.
+/// 커버가 없다는 결과도 캐시해서, 폴더가 바뀌기 전까지는 파일과 폴더를 다시 읽지 않는다.
+pub fn load_thumbnail(music_path: &Path, cache_path: &Path) -> anyhow::Result<Option<PathBuf>> {
+    let key = cache_key(music_path);
+    let thumbnail_path = cache_path.join(format!("{}.png", key));
+    let no_cover_path = cache_path.join(format!("{}.none", key));
+
+    if thumbnail_path.exists() {
+        return Ok(Some(thumbnail_path));
+    }
+
+    // 커버 이미지가 새로 생겼을 수 있으니 폴더 수정 시각이 같을 때만 캐시를 믿는다.
+    let folder_stamp = music_path.parent().map(modified_stamp).unwrap_or_default();
+
+    if fs::read_to_string(&no_cover_path).is_ok_and(|stamp| stamp == folder_stamp) {
+        return Ok(None);
+    }
+
+    let picture = match metadata::read_embedded_picture(music_path) {
+        Ok(Some(picture)) => Some(picture),
+        // 파일에 커버가 없으면 폴더의 커버 이미지를 사용
+        _ => match find_folder_image(music_path) {
+            Some(image_path) => Some(fs::read(image_path)?),
+            None => None,
+        },
+    };
+
+    fs::create_dir_all(cache_path)?;
+    // 정리할 때 원본이 남아 있는지 확인할 수 있도록 원본 경로를 함께 기록한다.
+    fs::write(
+        cache_path.join(format!("{}.source", key)),
+        music_path.to_string_lossy().as_bytes(),
+    )?;
+
+    let Some(picture) = picture else {
+        fs::write(&no_cover_path, folder_stamp)?;
+
+        return Ok(None);
+    };
+
+    let image = image::load_from_memory(&picture)?;
+
+    image
+        .thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE)
+        .save_with_format(&thumbnail_path, image::ImageFormat::Png)?;
+
+    Ok(Some(thumbnail_path))
+}
+
+/// 원본 파일이 없어졌거나 바뀐 캐시 항목을 지운다.
+/// 원본 경로가 기록되지 않은 파일(이전 형식의 캐시 등)도 함께 지운다.
+pub fn prune_cache(cache_path: &Path) -> anyhow::Result<()> {
+    let entry_paths = match fs::read_dir(cache_path) {
+        Ok(entries) => entries
+            .flatten()
+            .map(|entry| entry.path())
+            .collect::<Vec<_>>(),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(error) => return Err(error.into()),
+    };
+
+    let valid_keys = entry_paths
+        .iter()
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "source")
+        })
+        .filter_map(|path| {
+            let key = path.file_stem()?.to_str()?;
+            let music_path = PathBuf::from(fs::read_to_string(path).ok()?);
+
+            (music_path.exists() && cache_key(&music_path) == key).then(|| key.to_string())
+        })
+        .collect::<HashSet<_>>();
+
+    for path in entry_paths {
+        let is_valid = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .is_some_and(|stem| valid_keys.contains(stem));
+
+        if !is_valid && path.is_file() {
+            fs::remove_file(&path)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// 파일이 바뀌면 다시 만들도록 경로와 수정 시각으로 캐시 키를 만든다.
+/// 러스트 버전이 바뀌어도 키가 그대로 유지되도록 고정된 해시(FNV-1a 64비트)를 쓴다.
+fn cache_key(music_path: &Path) -> String {
+    let mut hash = fnv1a(FNV_OFFSET_BASIS, music_path.as_os_str().as_encoded_bytes());
+
+    hash = fnv1a(hash, &[0]);
+    hash = fnv1a(hash, modified_stamp(music_path).as_bytes());
+
+    format!("{:016x}", hash)
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+    for byte in bytes {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+
+    hash
+}
+
+/// 수정 시각을 "초.나노초" 문자열로 반환한다. (읽을 수 없으면 빈 문자열)
+fn modified_stamp(path: &Path) -> String {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| format!("{}.{:09}", duration.as_secs(), duration.subsec_nanos()))
+        .unwrap_or_default()
+}
+
+/// 곡과 같은 폴더에서 커버 이미지를 찾는다. (대소문자 구분 없음)
+fn find_folder_image(music_path: &Path) -> Option<PathBuf> {
+    let directory = music_path.parent()?;
+
+    let image_paths = fs::read_dir(directory)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .collect::<Vec<_>>();
+
+    FOLDER_IMAGE_NAMES.iter().find_map(|name| {
+        image_paths
+            .iter()
+            .find(|path| {
+                path.file_name()
+                    .and_then(|file_name| file_name.to_str())
+                    .is_some_and(|file_name| file_name.eq_ignore_ascii_case(name))
+            })
+            .cloned()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn falls_back_to_folder_image_and_caches_thumbnail() {
+        let directory =
+            std::env::temp_dir().join(format!("musica-album-art-{}", std::process::id()));
+        let cache_path = directory.join("cache");
+        fs::create_dir_all(&directory).unwrap();
+
+        let music_path = directory.join("song.mp3");
+        fs::write(&music_path, b"not really audio").unwrap();
+        image::RgbImage::new(300, 200)
+            .save(directory.join("Cover.JPG"))
+            .unwrap();
+
+        let thumbnail_path = load_thumbnail(&music_path, &cache_path).unwrap().unwrap();
+        let thumbnail = image::open(&thumbnail_path).unwrap();
+
+        assert!(thumbnail_path.starts_with(&cache_path));
+        assert_eq!((128, 85), (thumbnail.width(), thumbnail.height()));
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn caches_missing_cover_until_folder_changes() {
+        let directory =
+            std::env::temp_dir().join(format!("musica-no-album-art-{}", std::process::id()));
+        let music_directory = directory.join("music");
+        let cache_path = directory.join("cache");
+        fs::create_dir_all(&music_directory).unwrap();
+
+        let music_path = music_directory.join("song.mp3");
+        fs::write(&music_path, b"not really audio").unwrap();
+
+        assert_eq!(None, load_thumbnail(&music_path, &cache_path).unwrap());
+        assert!(
+            cache_path
+                .join(format!("{}.none", cache_key(&music_path)))
+                .exists()
+        );
+        assert_eq!(None, load_thumbnail(&music_path, &cache_path).unwrap());
+
+        // 폴더에 커버가 생기면 다시 찾아본다.
+        image::RgbImage::new(10, 10)
+            .save(music_directory.join("folder.png"))
+            .unwrap();
+        assert!(load_thumbnail(&music_path, &cache_path).unwrap().is_some());
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn prunes_cache_entries_without_source() {
+        let directory =
+            std::env::temp_dir().join(format!("musica-prune-album-art-{}", std::process::id()));
+        let cache_path = directory.join("cache");
+        fs::create_dir_all(&directory).unwrap();
+        image::RgbImage::new(10, 10)
+            .save(directory.join("cover.png"))
+            .unwrap();
+
+        let kept_path = directory.join("kept.mp3");
+        let removed_path = directory.join("removed.mp3");
+        fs::write(&kept_path, b"not really audio").unwrap();
+        fs::write(&removed_path, b"not really audio").unwrap();
+
+        let kept_thumbnail = load_thumbnail(&kept_path, &cache_path).unwrap().unwrap();
+        let removed_thumbnail = load_thumbnail(&removed_path, &cache_path).unwrap().unwrap();
+        let legacy_thumbnail = cache_path.join("0123456789abcdef.png");
+        fs::write(&legacy_thumbnail, b"").unwrap();
+        fs::remove_file(&removed_path).unwrap();
+
+        prune_cache(&cache_path).unwrap();
+
+        assert!(kept_thumbnail.exists());
+        assert!(!removed_thumbnail.exists());
+        assert!(!legacy_thumbnail.exists());
+        assert_eq!(2, fs::read_dir(&cache_path).unwrap().count());
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn cache_key_uses_fixed_hash() {
+        assert_eq!(0xaf63_dc4c_8601_ec8c, fnv1a(FNV_OFFSET_BASIS, b"a"));
+    }
+}
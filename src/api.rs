@@ -0,0 +1,692 @@
+use std::io::Read;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::Duration;
+
+use musica_core::backend::state::{BackgroundState, BackgroundStatusEvent};
+use musica_core::config::ApiConfig;
+use tiny_http::{Header, Method, Request, Response, Server, StatusCode};
+use tungstenite::protocol::Role;
+use tungstenite::{Message, WebSocket};
+
+use crate::control::StatusRelay;
+use crate::ipc::{IpcRequest, IpcResponse, IpcSender, PlayerStatus};
+
+/// 보낼 상태 변화가 없을 때 WebSocket 연결이 살아있는지 확인하는 간격
+const WEBSOCKET_PING_INTERVAL: Duration = Duration::from_secs(30);
+
+/// WebSocket으로 보내는 메시지 (type 필드로 구분)
+#[derive(Debug, serde::Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum PushMessage {
+    Status(PlayerStatus), // 곡, 재생 상태, 볼륨, 랜덤 모드가 바뀔 때
+    Position {
+        position_millis: u64,
+        duration_millis: Option<u64>,
+    },
+}
+
+/// localhost에 HTTP API 서버를 열고 실제로 열린 주소를 반환한다. (port가 0이면 임의의 포트)
+/// 요청은 소켓/MPRIS와 같은 경로로 처리하고, /api/ws 에서는 재생 상태 변화를 WebSocket으로 보낸다.
+/// 토큰이 없으면 열지 않는다. 토큰은 Authorization: Bearer 헤더로 보내고,
+/// 헤더를 붙일 수 없는 브라우저의 WebSocket만 /api/ws?token=... 으로 보낼 수 있다.
+///
+/// - GET    /api/status                  현재 상태
+/// - POST   /api/{play,pause,toggle,stop,next,previous}
+/// - POST   /api/volume  {"volume": 0.5}
+/// - POST   /api/seek    {"offset_millis": -5000}
+/// - POST   /api/shuffle {"shuffle": true}
+/// - GET    /api/library                 음악 폴더의 음악 파일
+/// - GET    /api/queue                   재생 목록
+/// - POST   /api/queue   {"paths": [...], "enqueue": true}
+/// - POST   /api/queue/{index}/play
+/// - DELETE /api/queue/{index}
+/// - DELETE /api/queue                   재생 목록 비우기
+pub fn serve(
+    api_config: &ApiConfig,
+    ipc_sender: IpcSender,
+    background_state: BackgroundState,
+) -> anyhow::Result<SocketAddr> {
+    if api_config.token.as_deref().is_none_or(str::is_empty) {
+        anyhow::bail!("HTTP API requires a token");
+    }
+
+    let server = Server::http((Ipv4Addr::LOCALHOST, api_config.port))
+        .map_err(|error| anyhow::anyhow!(error))?;
+
+    let address = server
+        .server_addr()
+        .to_ip()
+        .ok_or_else(|| anyhow::anyhow!("HTTP server is not bound to an IP address"))?;
+    let api_config = Arc::new(api_config.clone());
+    let status_relay = StatusRelay::start(&background_state);
+
+    std::thread::spawn(move || {
+        for request in server.incoming_requests() {
+            let api_config = api_config.clone();
+            let ipc_sender = ipc_sender.clone();
+            let status_relay = status_relay.clone();
+
+            // 응답을 기다리는 요청이나 WebSocket이 다른 요청을 막지 않도록 요청마다 스레드를 쓴다.
+            std::thread::spawn(move || {
+                if let Err(error) = handle_request(request, &api_config, ipc_sender, &status_relay)
+                {
+                    log::error!("Failed to handle HTTP request: {:?}", error);
+                }
+            });
+        }
+    });
+
+    Ok(address)
+}
+
+fn handle_request(
+    mut request: Request,
+    api_config: &ApiConfig,
+    ipc_sender: IpcSender,
+    status_relay: &StatusRelay,
+) -> anyhow::Result<()> {
+    // 다른 이름으로 localhost를 가리키게 하는 DNS 리바인딩을 막는다.
+    if !is_local_host(&request) {
+        let response = json_response(403, &IpcResponse::error("Forbidden host"), None);
+
+        return Ok(request.respond(response)?);
+    }
+
+    // 브라우저에서 온 요청(WebSocket 포함)은 허용한 Origin에서 온 것만 받는다.
+    let origin = header_value(&request, "Origin");
+    if let Some(origin) = &origin
+        && !api_config.allowed_origins.contains(origin)
+    {
+        let response = json_response(403, &IpcResponse::error("Forbidden origin"), None);
+
+        return Ok(request.respond(response)?);
+    }
+    let origin = origin.as_deref();
+
+    // 브라우저의 CORS 사전 요청에는 토큰이 붙지 않는다.
+    if *request.method() == Method::Options {
+        return Ok(request.respond(with_cors(Response::empty(204), origin))?);
+    }
+
+    let url = request.url().to_string();
+    let path = url.split('?').next().unwrap_or_default();
+    let segments = path
+        .trim_matches('/')
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>();
+    let is_websocket = segments == ["api", "ws"];
+
+    if !is_authorized(
+        &request,
+        api_config.token.as_deref().unwrap_or_default(),
+        is_websocket,
+    ) {
+        let response = json_response(401, &IpcResponse::error("Unauthorized"), origin);
+
+        return Ok(request.respond(response)?);
+    }
+
+    if is_websocket {
+        return upgrade_to_websocket(request, ipc_sender, status_relay);
+    }
+
+    let mut body = String::new();
+    request.as_reader().read_to_string(&mut body)?;
+
+    let ipc_request = match (request.method(), segments.as_slice()) {
+        (Method::Get, ["api", "status"]) => Ok(IpcRequest::Status),
+        (Method::Post, ["api", "play"]) => Ok(IpcRequest::Play),
+        (Method::Post, ["api", "pause"]) => Ok(IpcRequest::Pause),
+        (Method::Post, ["api", "toggle"]) => Ok(IpcRequest::Toggle),
+        (Method::Post, ["api", "stop"]) => Ok(IpcRequest::Stop),
+        (Method::Post, ["api", "next"]) => Ok(IpcRequest::Next),
+        (Method::Post, ["api", "previous"]) => Ok(IpcRequest::Previous),
+        (Method::Post, ["api", command @ ("volume" | "seek" | "shuffle")]) => {
+            parse_body(command, &body)
+        }
+        (Method::Get, ["api", "library"]) => Ok(IpcRequest::Library),
+        (Method::Get, ["api", "queue"]) => Ok(IpcRequest::Queue),
+        (Method::Post, ["api", "queue"]) => parse_body("open", &body),
+        (Method::Delete, ["api", "queue"]) => Ok(IpcRequest::Clear),
+        (Method::Post, ["api", "queue", index, "play"]) => {
+            parse_index(index).map(|index| IpcRequest::PlayIndex { index })
+        }
+        (Method::Delete, ["api", "queue", index]) => {
+            parse_index(index).map(|index| IpcRequest::Remove { index })
+        }
+        _ => {
+            let response = json_response(404, &IpcResponse::error("Not found"), origin);
+
+            return Ok(request.respond(response)?);
+        }
+    };
+
+    let response = match ipc_request {
+        Ok(ipc_request) => ipc_sender.blocking_request(ipc_request),
+        Err(error) => IpcResponse::error(error),
+    };
+    let status_code = if response.ok { 200 } else { 400 };
+
+    Ok(request.respond(json_response(status_code, &response, origin))?)
+}
+
+/// Host 헤더가 localhost나 127.0.0.1인지 확인한다. (포트는 상관없음)
+fn is_local_host(request: &Request) -> bool {
+    let Some(host) = header_value(request, "Host") else {
+        return false;
+    };
+
+    let host_name = match host.rsplit_once(':') {
+        Some((host_name, port)) if port.chars().all(|char| char.is_ascii_digit()) => host_name,
+        _ => host.as_str(),
+    };
+
+    host_name.eq_ignore_ascii_case("localhost") || host_name == "127.0.0.1"
+}
+
+/// Authorization: Bearer 헤더로 토큰을 확인한다.
+/// 브라우저의 WebSocket은 헤더를 붙일 수 없어서 accepts_query_token이면 token 쿼리 파라미터도 받는다.
+/// (주소에 남는 토큰이 다른 요청에 쓰이지 않도록 WebSocket 연결에서만 받는다.)
+fn is_authorized(request: &Request, token: &str, accepts_query_token: bool) -> bool {
+    if token.is_empty() {
+        return false;
+    }
+
+    let header_value = header_value(request, "Authorization");
+    let header_token = header_value
+        .as_deref()
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    let query_token = request
+        .url()
+        .split_once('?')
+        .filter(|_| accepts_query_token)
+        .and_then(|(_, query)| {
+            query
+                .split('&')
+                .find_map(|parameter| parameter.strip_prefix("token="))
+        });
+
+    [header_token, query_token]
+        .into_iter()
+        .flatten()
+        .any(|given| is_same_token(token, given))
+}
+
+/// 비교에 걸리는 시간으로 토큰이 몇 글자까지 맞는지 알아낼 수 없도록 끝까지 비교한다.
+pub fn is_same_token(token: &str, given: &str) -> bool {
+    token.len() == given.len()
+        && token
+            .bytes()
+            .zip(given.bytes())
+            .fold(0, |difference, (a, b)| difference | (a ^ b))
+            == 0
+}
+
+/// 요청 본문의 JSON 객체에 command를 붙여 IpcRequest로 읽는다.
+fn parse_body(command: &str, body: &str) -> Result<IpcRequest, String> {
+    let mut value = if body.trim().is_empty() {
+        serde_json::json!({})
+    } else {
+        serde_json::from_str(body).map_err(|error| format!("Invalid body: {error}"))?
+    };
+
+    let object = value
+        .as_object_mut()
+        .ok_or_else(|| "Body must be a JSON object".to_string())?;
+    object.insert("command".into(), command.into());
+
+    // 재생 목록에 추가하는 요청은 따로 지정하지 않으면 바로 재생하지 않는다.
+    if command == "open" {
+        object.entry("enqueue").or_insert(true.into());
+    }
+
+    serde_json::from_value(value).map_err(|error| format!("Invalid body: {error}"))
+}
+
+fn parse_index(index: &str) -> Result<usize, String> {
+    index
+        .parse()
+        .map_err(|_| format!("Invalid queue index: {index}"))
+}
+
+fn json_response<T: serde::Serialize>(
+    status_code: u16,
+    value: &T,
+    origin: Option<&str>,
+) -> Response<std::io::Cursor<Vec<u8>>> {
+    let body = serde_json::to_vec(value).unwrap_or_default();
+
+    with_cors(
+        Response::from_data(body)
+            .with_status_code(status_code)
+            .with_header(header("Content-Type", "application/json")),
+        origin,
+    )
+}
+
+/// 허용한 Origin에서 띄운 웹 리모컨 페이지가 응답을 읽을 수 있도록 CORS 헤더를 붙인다.
+/// (origin은 allowed_origins에서 확인한 값, 브라우저가 아닌 요청이면 None)
+fn with_cors<R: Read>(response: Response<R>, origin: Option<&str>) -> Response<R> {
+    let Some(origin) = origin else {
+        return response;
+    };
+
+    response
+        .with_header(header("Access-Control-Allow-Origin", origin))
+        .with_header(header("Vary", "Origin"))
+        .with_header(header(
+            "Access-Control-Allow-Methods",
+            "GET, POST, DELETE, OPTIONS",
+        ))
+        .with_header(header(
+            "Access-Control-Allow-Headers",
+            "Authorization, Content-Type",
+        ))
+}
+
+fn header(field: &str, value: &str) -> Header {
+    Header::from_bytes(field.as_bytes(), value.as_bytes()).expect("valid header")
+}
+
+fn header_value(request: &Request, field: &'static str) -> Option<String> {
+    request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv(field))
+        .map(|header| header.value.to_string())
+}
+
+/// WebSocket으로 전환하고, 연결이 끊길 때까지 재생 상태 변화를 보낸다.
+fn upgrade_to_websocket(
+    request: Request,
+    ipc_sender: IpcSender,
+    status_relay: &StatusRelay,
+) -> anyhow::Result<()> {
+    let is_upgrade = header_value(&request, "Upgrade")
+        .is_some_and(|upgrade| upgrade.eq_ignore_ascii_case("websocket"));
+    let key = header_value(&request, "Sec-WebSocket-Key");
+    let (true, Some(key)) = (is_upgrade, key) else {
+        let response = json_response(
+            400,
+            &IpcResponse::error("Expected a WebSocket request"),
+            None,
+        );
+
+        return Ok(request.respond(response)?);
+    };
+
+    if header_value(&request, "Sec-WebSocket-Version").as_deref() != Some("13") {
+        let response = json_response(
+            426,
+            &IpcResponse::error("Unsupported WebSocket version"),
+            None,
+        )
+        .with_header(header("Sec-WebSocket-Version", "13"));
+
+        return Ok(request.respond(response)?);
+    }
+
+    // 업그레이드 전에 구독해야 첫 상태를 보내는 사이의 변화를 놓치지 않는다.
+    let (event_sender, events) = mpsc::channel();
+    status_relay.subscribe(move |event| event_sender.send(event.clone()).is_ok());
+
+    let response = Response::empty(StatusCode(101)).with_header(header(
+        "Sec-WebSocket-Accept",
+        &tungstenite::handshake::derive_accept_key(key.as_bytes()),
+    ));
+    let stream = request.upgrade("websocket", response);
+    let mut websocket = WebSocket::from_raw_socket(stream, Role::Server, None);
+
+    send_status(&mut websocket, &ipc_sender)?;
+
+    loop {
+        let event = match events.recv_timeout(WEBSOCKET_PING_INTERVAL) {
+            Ok(event) => event,
+            // 보낼 것이 없어도 ping을 보내서, 끊긴 연결은 보내기가 실패할 때 정리한다.
+            Err(RecvTimeoutError::Timeout) => {
+                websocket.send(Message::Ping(Default::default()))?;
+                continue;
+            }
+            Err(RecvTimeoutError::Disconnected) => break,
+        };
+
+        match event {
+            BackgroundStatusEvent::MusicChanged(_)
+            | BackgroundStatusEvent::PlaylistChanged
+            | BackgroundStatusEvent::PauseChanged(_)
+            | BackgroundStatusEvent::StopChanged(_)
+            | BackgroundStatusEvent::VolumeChanged(_)
+            | BackgroundStatusEvent::RandomModeChanged(_) => {
+                send_status(&mut websocket, &ipc_sender)?
+            }
+            BackgroundStatusEvent::PositionChanged { position, duration } => send(
+                &mut websocket,
+                &PushMessage::Position {
+                    position_millis: position.as_millis() as u64,
+                    duration_millis: duration.map(|duration| duration.as_millis() as u64),
+                },
+            )?,
+            _ => {}
+        }
+    }
+
+    let _ = websocket.close(None);
+
+    Ok(())
+}
+
+fn send_status<S: std::io::Read + std::io::Write>(
+    websocket: &mut WebSocket<S>,
+    ipc_sender: &IpcSender,
+) -> anyhow::Result<()> {
+    let response = ipc_sender.blocking_request(IpcRequest::Status);
+
+    match response.status {
+        Some(status) => send(websocket, &PushMessage::Status(status)),
+        None => anyhow::bail!(response.error.unwrap_or_default()),
+    }
+}
+
+fn send<S: std::io::Read + std::io::Write>(
+    websocket: &mut WebSocket<S>,
+    message: &PushMessage,
+) -> anyhow::Result<()> {
+    let text = serde_json::to_string(message)?;
+
+    // 클라이언트가 연결을 끊었으면 여기서 오류가 나서 전송 루프가 끝난다.
+    websocket.send(Message::text(text))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+    use std::net::TcpStream;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use super::*;
+    use crate::ipc::{self, PlaybackState};
+
+    fn start() -> (SocketAddr, BackgroundState, Arc<Mutex<Vec<IpcRequest>>>) {
+        let (ipc_sender, requests) = ipc::spawn_fake_frontend(
+            PlayerStatus {
+                state: PlaybackState::Playing,
+                index: Some(0),
+                title: Some("a.mp3".into()),
+                artist: None,
+                album: None,
+                file_path: Some("/music/a.mp3".into()),
+                position_millis: 0,
+                duration_millis: None,
+                volume: 0.5,
+                is_muted: false,
+                shuffle: false,
+                stop_after_current: false,
+                queue_length: 1,
+            },
+            Vec::new(),
+        );
+        let background_state = BackgroundState::new(false);
+        let api_config = ApiConfig {
+            enabled: true,
+            port: 0,
+            token: Some("secret".into()),
+            allowed_origins: vec!["http://localhost:8080".into()],
+        };
+
+        let address = serve(&api_config, ipc_sender, background_state.clone()).unwrap();
+
+        (address, background_state, requests)
+    }
+
+    /// Host: localhost로 HTTP 요청을 보내고 (상태 코드, 본문)을 반환한다.
+    fn http(address: SocketAddr, request_line: &str, headers: &str, body: &str) -> (u16, String) {
+        let response = raw_http(address, "localhost", request_line, headers, body);
+
+        let status_code = response[9..12].parse().unwrap();
+        let body = response
+            .split_once("\r\n\r\n")
+            .map(|(_, body)| body.to_string())
+            .unwrap_or_default();
+
+        (status_code, body)
+    }
+
+    /// HTTP 요청을 보내고 헤더를 포함한 응답 전체를 반환한다.
+    fn raw_http(
+        address: SocketAddr,
+        host: &str,
+        request_line: &str,
+        headers: &str,
+        body: &str,
+    ) -> String {
+        let mut stream = TcpStream::connect(address).unwrap();
+        stream
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+
+        write!(
+            stream,
+            "{request_line} HTTP/1.1\r\nHost: {host}\r\nConnection: close\r\n{headers}Content-Length: {}\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        response
+    }
+
+    #[test]
+    fn refuses_to_start_without_token() {
+        let (ipc_sender, _ipc_receiver) = ipc::channel();
+        let api_config = ApiConfig {
+            enabled: true,
+            port: 0,
+            token: None,
+            allowed_origins: Vec::new(),
+        };
+
+        assert!(serve(&api_config, ipc_sender, BackgroundState::new(false)).is_err());
+    }
+
+    #[test]
+    fn rejects_foreign_hosts_and_origins() {
+        let (address, _background_state, requests) = start();
+
+        let response = raw_http(
+            address,
+            "evil.example",
+            "GET /api/status",
+            "Authorization: Bearer secret\r\n",
+            "",
+        );
+        assert!(response.starts_with("HTTP/1.1 403"), "{response}");
+        assert!(
+            raw_http(
+                address,
+                &format!("127.0.0.1:{}", address.port()),
+                "GET /api/status",
+                "Authorization: Bearer secret\r\n",
+                ""
+            )
+            .starts_with("HTTP/1.1 200")
+        );
+
+        assert_eq!(
+            403,
+            http(
+                address,
+                "POST /api/next",
+                "Origin: http://evil.example\r\nAuthorization: Bearer secret\r\n",
+                ""
+            )
+            .0
+        );
+        // 브라우저의 WebSocket도 Origin을 확인한다.
+        assert_eq!(
+            403,
+            http(
+                address,
+                "GET /api/ws?token=secret",
+                "Origin: http://evil.example\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\nSec-WebSocket-Version: 13\r\n",
+                ""
+            )
+            .0
+        );
+
+        let response = raw_http(
+            address,
+            "localhost",
+            "OPTIONS /api/next",
+            "Origin: http://localhost:8080\r\n",
+            "",
+        );
+        assert!(response.starts_with("HTTP/1.1 204"), "{response}");
+        assert!(response.contains("Access-Control-Allow-Origin: http://localhost:8080"));
+        assert!(!response.contains("Access-Control-Allow-Origin: *"));
+
+        assert!(requests.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn requires_token_and_forwards_requests() {
+        let (address, _background_state, requests) = start();
+
+        assert_eq!(401, http(address, "GET /api/status", "", "").0);
+        assert_eq!(
+            401,
+            http(
+                address,
+                "GET /api/status",
+                "Authorization: Bearer wrong\r\n",
+                ""
+            )
+            .0
+        );
+
+        let (status_code, body) = http(
+            address,
+            "GET /api/status",
+            "Authorization: Bearer secret\r\n",
+            "",
+        );
+        let response: IpcResponse = serde_json::from_str(&body).unwrap();
+        assert_eq!(200, status_code);
+        assert_eq!(Some("a.mp3".to_string()), response.status.unwrap().title);
+
+        // 쿼리 파라미터의 토큰은 WebSocket 연결에서만 받는다.
+        assert_eq!(401, http(address, "POST /api/next?token=secret", "", "").0);
+
+        let authorization = "Authorization: Bearer secret\r\n";
+        assert_eq!(
+            200,
+            http(
+                address,
+                "POST /api/volume",
+                authorization,
+                r#"{"volume":0.25}"#
+            )
+            .0
+        );
+        assert_eq!(
+            200,
+            http(
+                address,
+                "POST /api/queue",
+                authorization,
+                r#"{"paths":["/music/a.mp3"]}"#
+            )
+            .0
+        );
+        assert_eq!(
+            200,
+            http(address, "DELETE /api/queue/3", authorization, "").0
+        );
+        assert_eq!(
+            400,
+            http(address, "POST /api/volume", authorization, "{}").0
+        );
+        assert_eq!(404, http(address, "GET /api/unknown", authorization, "").0);
+
+        assert_eq!(
+            vec![
+                IpcRequest::Volume { volume: 0.25 },
+                IpcRequest::Open {
+                    paths: vec!["/music/a.mp3".into()],
+                    enqueue: true,
+                },
+                IpcRequest::Remove { index: 3 },
+            ],
+            *requests.lock().unwrap()
+        );
+    }
+
+    #[test]
+    fn compares_whole_token() {
+        assert!(is_same_token("secret", "secret"));
+        assert!(!is_same_token("secret", "secreT"));
+        assert!(!is_same_token("secret", "secret2"));
+        assert!(!is_same_token("secret", ""));
+    }
+
+    #[test]
+    fn rejects_incomplete_websocket_upgrades() {
+        let (address, _background_state, _requests) = start();
+
+        assert_eq!(
+            400,
+            http(
+                address,
+                "GET /api/ws?token=secret",
+                "Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\nSec-WebSocket-Version: 13\r\n",
+                ""
+            )
+            .0
+        );
+
+        let response = raw_http(
+            address,
+            "localhost",
+            "GET /api/ws?token=secret",
+            "Upgrade: websocket\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\nSec-WebSocket-Version: 8\r\n",
+            "",
+        );
+        assert!(response.starts_with("HTTP/1.1 426"), "{response}");
+        assert!(response.contains("Sec-WebSocket-Version: 13"));
+    }
+
+    #[test]
+    fn pushes_status_and_position_over_websocket() {
+        let (address, background_state, _requests) = start();
+
+        let (mut websocket, _) =
+            tungstenite::connect(format!("ws://{address}/api/ws?token=secret")).unwrap();
+
+        let read_json = |websocket: &mut WebSocket<_>| -> serde_json::Value {
+            let message = websocket.read().unwrap();
+            serde_json::from_str(message.to_text().unwrap()).unwrap()
+        };
+
+        let status = read_json(&mut websocket);
+        assert_eq!("status", status["type"]);
+        assert_eq!("a.mp3", status["title"]);
+
+        background_state.publish(BackgroundStatusEvent::PositionChanged {
+            position: Duration::from_millis(1_500),
+            duration: Some(Duration::from_secs(3)),
+        });
+
+        let position = read_json(&mut websocket);
+        assert_eq!("position", position["type"]);
+        assert_eq!(1_500, position["position_millis"]);
+        assert_eq!(3_000, position["duration_millis"]);
+    }
+}
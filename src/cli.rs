@@ -0,0 +1,303 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use clap::{Parser, Subcommand};
+use musica_core::config;
+use musica_core::player::PlayerOptions;
+use musica_core::state::format_duration;
+
+use crate::ipc::{self, IpcRequest, PlaybackState, PlayerStatus};
+
+/// 명령줄 인자
+/// 옵션은 이번 실행에만 적용하고 설정 파일에는 저장하지 않는다.
+#[derive(Debug, Clone, Parser)]
+#[command(version, about, args_conflicts_with_subcommands = true)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    // 지정하면 설정의 음악 폴더와 지난 세션 대신 재생
+    #[arg(help = "Directories, music files or .m3u playlists to play")]
+    pub paths: Vec<PathBuf>,
+
+    #[arg(long, conflicts_with = "no_shuffle", help = "Play in random order")]
+    pub shuffle: bool,
+
+    #[arg(long, help = "Play in list order")]
+    pub no_shuffle: bool,
+
+    #[arg(long, value_parser = parse_volume, help = "Volume (0.0 - 1.0)")]
+    pub volume: Option<f32>,
+
+    #[arg(long, help = "Open the first music paused")]
+    pub no_autoplay: bool,
+
+    // 이미 실행 중인 musica에 넘길 때 바로 재생하지 않고 목록에만 추가
+    #[arg(long, help = "Only add the paths to the running musica's list")]
+    pub enqueue: bool,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Use this config file instead of the default one"
+    )]
+    pub config: Option<PathBuf>,
+
+    // 창 없이 실행하고 ctl, MPRIS, HTTP API, MPD로만 제어 (음악 서버용)
+    #[arg(long, help = "Run without a window, controlled only remotely")]
+    pub headless: bool,
+
+    // SSH 등 터미널에서 사용
+    #[arg(long, conflicts_with = "headless", help = "Run in the terminal")]
+    pub tui: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Subcommand)]
+pub enum Command {
+    /// Control the running musica
+    Ctl {
+        #[command(subcommand)]
+        command: CtlCommand,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Subcommand)]
+pub enum CtlCommand {
+    /// Resume playback
+    Play,
+    /// Pause playback
+    Pause,
+    /// Toggle between play and pause
+    Toggle,
+    /// Stop playback
+    Stop,
+    /// Play the next music
+    Next,
+    /// Play the previous music
+    Previous,
+    /// Set the volume (0.0 - 1.0)
+    Volume {
+        #[arg(value_parser = parse_volume)]
+        volume: f32,
+    },
+    /// Seek forward (or backward with a negative value) in seconds
+    Seek {
+        #[arg(allow_negative_numbers = true)]
+        offset_secs: f64,
+    },
+    /// Print what is playing
+    Status {
+        #[arg(long, help = "Print as JSON")]
+        json: bool,
+    },
+    /// Quit the running musica
+    Quit,
+}
+
+impl CtlCommand {
+    pub fn request(&self) -> IpcRequest {
+        match self {
+            CtlCommand::Play => IpcRequest::Play,
+            CtlCommand::Pause => IpcRequest::Pause,
+            CtlCommand::Toggle => IpcRequest::Toggle,
+            CtlCommand::Stop => IpcRequest::Stop,
+            CtlCommand::Next => IpcRequest::Next,
+            CtlCommand::Previous => IpcRequest::Previous,
+            CtlCommand::Volume { volume } => IpcRequest::Volume { volume: *volume },
+            CtlCommand::Seek { offset_secs } => IpcRequest::Seek {
+                offset_millis: (offset_secs * 1000.0) as i64,
+            },
+            CtlCommand::Status { .. } => IpcRequest::Status,
+            CtlCommand::Quit => IpcRequest::Quit,
+        }
+    }
+}
+
+/// 실행 중인 인스턴스에 명령을 보내고 결과를 출력한다. 프로세스 종료 코드를 반환한다.
+pub fn run_ctl(command: &CtlCommand) -> i32 {
+    let response = match ipc::send_request(&ipc::get_socket_path(), &command.request()) {
+        Ok(response) => response,
+        Err(error) if ipc::is_not_running(&error) => {
+            eprintln!("musica is not running: {error}");
+            return 1;
+        }
+        Err(error) => {
+            eprintln!("Failed to reach musica: {error}");
+            return 1;
+        }
+    };
+
+    if let Some(error) = response.error {
+        eprintln!("{error}");
+        return 1;
+    }
+
+    if let (CtlCommand::Status { json }, Some(status)) = (command, response.status) {
+        if *json {
+            match serde_json::to_string_pretty(&status) {
+                Ok(status_str) => println!("{status_str}"),
+                Err(error) => {
+                    eprintln!("{error}");
+                    return 1;
+                }
+            }
+        } else {
+            println!("{}", format_status(&status));
+        }
+    }
+
+    0
+}
+
+/// 사람이 읽을 상태 한 줄 ("[playing] Title - Artist 1:23/3:45 vol 40%")
+fn format_status(status: &PlayerStatus) -> String {
+    let state = match status.state {
+        PlaybackState::Playing => "playing",
+        PlaybackState::Paused => "paused",
+        PlaybackState::Stopped => "stopped",
+    };
+
+    let mut line = format!("[{state}]");
+
+    if let Some(title) = &status.title {
+        line.push_str(&format!(" {title}"));
+    }
+
+    if let Some(artist) = &status.artist {
+        line.push_str(&format!(" - {artist}"));
+    }
+
+    line.push_str(&format!(
+        " {}",
+        format_duration(Duration::from_millis(status.position_millis))
+    ));
+
+    if let Some(duration_millis) = status.duration_millis {
+        line.push_str(&format!(
+            "/{}",
+            format_duration(Duration::from_millis(duration_millis))
+        ));
+    }
+
+    if status.is_muted {
+        line.push_str(" muted");
+    } else {
+        line.push_str(&format!(" vol {:.0}%", status.volume * 100.0));
+    }
+
+    if status.shuffle {
+        line.push_str(" shuffle");
+    }
+
+    line
+}
+
+impl Cli {
+    /// 실행 중인 인스턴스는 작업 폴더가 다를 수 있으므로 절대 경로로 바꾼다.
+    pub fn absolute_paths(&self) -> Vec<PathBuf> {
+        self.paths
+            .iter()
+            .map(|path| std::path::absolute(path).unwrap_or_else(|_| path.clone()))
+            .collect()
+    }
+
+    /// 재생 엔진을 시작할 옵션 (설정 파일 대신 쓸 값은 저장하지 않는다.)
+    pub fn player_options(&self) -> PlayerOptions {
+        let is_random = if self.shuffle {
+            Some(true)
+        } else if self.no_shuffle {
+            Some(false)
+        } else {
+            None
+        };
+
+        PlayerOptions {
+            config_path: self.config.clone().unwrap_or_else(config::get_config_path),
+            paths: self.paths.clone(),
+            is_random,
+            volume: self.volume,
+            no_autoplay: self.no_autoplay,
+            ..Default::default()
+        }
+    }
+}
+
+fn parse_volume(value: &str) -> Result<f32, String> {
+    let volume: f32 = value
+        .parse()
+        .map_err(|_| format!("{value} is not a number"))?;
+
+    if (0.0..=1.0).contains(&volume) {
+        Ok(volume)
+    } else {
+        Err("volume must be between 0.0 and 1.0".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_paths_and_flags() {
+        let cli = Cli::try_parse_from([
+            "musica-app",
+            "--shuffle",
+            "--volume",
+            "0.4",
+            "--no-autoplay",
+            "--config",
+            "/tmp/config.json",
+            "a.mp3",
+            "list.m3u",
+        ])
+        .unwrap();
+
+        assert_eq!(
+            vec![PathBuf::from("a.mp3"), PathBuf::from("list.m3u")],
+            cli.paths
+        );
+        assert!(cli.shuffle);
+        assert_eq!(Some(0.4), cli.volume);
+        assert!(cli.no_autoplay);
+        assert_eq!(Some(PathBuf::from("/tmp/config.json")), cli.config);
+
+        let options = cli.player_options();
+        assert_eq!(PathBuf::from("/tmp/config.json"), options.config_path);
+        assert_eq!(Some(true), options.is_random);
+        assert_eq!(Some(0.4), options.volume);
+
+        let cli = Cli::try_parse_from(["musica-app", "--no-shuffle"]).unwrap();
+        assert_eq!(Some(false), cli.player_options().is_random);
+
+        assert!(Cli::try_parse_from(["musica-app", "--volume", "1.5"]).is_err());
+        assert!(Cli::try_parse_from(["musica-app", "--shuffle", "--no-shuffle"]).is_err());
+    }
+
+    #[test]
+    fn parses_ctl_subcommands() {
+        let ctl = |args: &[&str]| {
+            let cli = Cli::try_parse_from([&["musica-app", "ctl"], args].concat()).unwrap();
+
+            match cli.command {
+                Some(Command::Ctl { command }) => command.request(),
+                None => panic!("not a ctl command"),
+            }
+        };
+
+        assert_eq!(IpcRequest::Next, ctl(&["next"]));
+        assert_eq!(IpcRequest::Volume { volume: 0.4 }, ctl(&["volume", "0.4"]));
+        assert_eq!(
+            IpcRequest::Seek {
+                offset_millis: -5_000
+            },
+            ctl(&["seek", "-5"])
+        );
+        assert_eq!(IpcRequest::Status, ctl(&["status", "--json"]));
+
+        // 하위 명령이 아니면 재생할 경로로 읽는다.
+        let cli = Cli::try_parse_from(["musica-app", "next.mp3"]).unwrap();
+        assert_eq!(None, cli.command);
+        assert_eq!(vec![PathBuf::from("next.mp3")], cli.paths);
+    }
+}
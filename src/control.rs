@@ -0,0 +1,231 @@
+use std::sync::{Arc, Mutex, mpsc};
+
+use musica_core::backend::state::{BackgroundState, BackgroundStatusEvent};
+use musica_core::config::ApiConfig;
+use musica_core::player::Player;
+
+use crate::cli::Cli;
+use crate::ipc::{
+    IpcMessage, IpcReceiver, IpcRequest, IpcResponse, IpcSender, PlayerStatus, TrackInfo,
+};
+
+/// iced 구독 없이 Player를 구동하는 루프(헤드리스, TUI)가 받는 입력
+pub enum PlayerEvent {
+    IpcRequested(IpcMessage),
+    BackgroundStatus(BackgroundStatusEvent),
+    Quit, // 종료 시그널 등 응답할 필요가 없는 종료 요청
+}
+
+/// 상태 이벤트를 받아서 처리하고, 계속 받으려면 true를 반환하는 함수
+type StatusForwarder = Box<dyn FnMut(&BackgroundStatusEvent) -> bool + Send>;
+
+/// 재생 상태 변화를 연결마다 나눠 주는 스레드 (HTTP API의 WebSocket, MPD 연결용)
+/// 연결마다 상태 스트림을 기다리는 스레드를 두면 연결이 끊겨도 다음 이벤트가 올 때까지 스레드가 남으므로,
+/// 스레드 하나가 나눠 주고 더 받지 않겠다는 연결은 목록에서 지운다.
+#[derive(Clone)]
+pub struct StatusRelay {
+    forwarders: Arc<Mutex<Vec<StatusForwarder>>>,
+}
+
+impl StatusRelay {
+    pub fn start(background_state: &BackgroundState) -> Self {
+        let relay = Self {
+            forwarders: Arc::default(),
+        };
+
+        let status_stream = background_state.status_changes();
+        let forwarders = relay.forwarders.clone();
+
+        std::thread::spawn(move || {
+            for event in status_stream {
+                forwarders
+                    .lock()
+                    .unwrap()
+                    .retain_mut(|forward| forward(&event));
+            }
+        });
+
+        relay
+    }
+
+    /// 지금 이후의 상태 변화를 forward로 넘긴다. forward가 false를 반환하면 더 넘기지 않는다.
+    pub fn subscribe(&self, forward: impl FnMut(&BackgroundStatusEvent) -> bool + Send + 'static) {
+        self.forwarders.lock().unwrap().push(Box::new(forward));
+    }
+}
+
+/// 명령줄 옵션으로 재생 엔진을 시작하고 원격 제어 서버(HTTP API, MPD, MPRIS)를 연다.
+pub fn start_player(cli: &Cli, ipc_sender: IpcSender) -> Player {
+    let mut player = Player::new(cli.player_options());
+
+    // 웹 리모컨 등에서 쓰는 로컬 HTTP API
+    if player.config_data.api.enabled {
+        // 토큰 없이 열리지 않도록 처음 켤 때 토큰을 만들어 설정 파일에 저장한다.
+        if player.config_data.api.token.is_none() {
+            player.config_data.api.token = Some(ApiConfig::generate_token());
+            player.save_config();
+            log::info!("Generated an HTTP API token and saved it to the config file");
+        }
+
+        if let Err(error) = crate::api::serve(
+            &player.config_data.api,
+            ipc_sender.clone(),
+            player.background_state().clone(),
+        ) {
+            log::error!("Failed to start HTTP API: {:?}", error);
+        }
+    }
+
+    // MPD 클라이언트용 서버
+    if player.config_data.mpd.enabled
+        && let Err(error) = crate::mpd::serve(
+            &player.config_data.mpd,
+            player.config_data.api.token.clone(),
+            ipc_sender.clone(),
+            player.background_state().clone(),
+        )
+    {
+        log::error!("Failed to start MPD server: {:?}", error);
+    }
+
+    // 데스크톱 미디어 컨트롤에 노출
+    #[cfg(target_os = "linux")]
+    crate::mpris::serve(None, ipc_sender, player.background_state().clone());
+    #[cfg(not(target_os = "linux"))]
+    drop(ipc_sender);
+
+    player
+}
+
+/// 원격 요청과 백그라운드 루프의 상태 이벤트를 sender로 넘기는 스레드를 시작한다.
+pub fn forward_events<E>(player: &Player, ipc_receiver: &IpcReceiver, sender: mpsc::Sender<E>)
+where
+    E: From<PlayerEvent> + Send + 'static,
+{
+    // 수신자는 하나뿐이므로 꺼내서 전달 스레드로 옮긴다.
+    if let Some(mut ipc_receiver) = ipc_receiver.lock().unwrap().take() {
+        let sender = sender.clone();
+
+        std::thread::spawn(move || {
+            while let Some(message) = ipc_receiver.blocking_recv() {
+                if sender
+                    .send(PlayerEvent::IpcRequested(message).into())
+                    .is_err()
+                {
+                    return;
+                }
+            }
+        });
+    }
+
+    let status_stream = player.status_stream();
+
+    std::thread::spawn(move || {
+        for event in status_stream {
+            if sender
+                .send(PlayerEvent::BackgroundStatus(event).into())
+                .is_err()
+            {
+                return;
+            }
+        }
+    });
+}
+
+/// 넘겨받은 이벤트를 처리한다. quit 요청이면 응답한 뒤 true를 반환한다.
+pub fn handle_event(player: &mut Player, event: PlayerEvent) -> bool {
+    match event {
+        PlayerEvent::IpcRequested(message) => {
+            let response = handle_request(player, &message.request);
+            message.reply(response);
+
+            message.request == IpcRequest::Quit
+        }
+        PlayerEvent::BackgroundStatus(event) => {
+            player.handle_status(&event);
+
+            false
+        }
+        PlayerEvent::Quit => true,
+    }
+}
+
+/// 다른 실행(두 번째 실행, ctl 명령, MPRIS, HTTP API, MPD)에서 보낸 요청을 처리한다.
+/// quit 요청은 응답만 하고, 종료는 요청을 받은 쪽(화면, 헤드리스 루프)이 한다.
+pub fn handle_request(player: &mut Player, request: &IpcRequest) -> IpcResponse {
+    match request {
+        IpcRequest::Open { paths, enqueue } => player.open_paths(paths, *enqueue),
+        IpcRequest::Status => {
+            let status = PlayerStatus::read(
+                player.background_state(),
+                &player.main_state.music_list,
+                player.config_data.volume,
+                player.main_state.is_muted,
+            );
+
+            return IpcResponse::status(status);
+        }
+        // 이미 그 상태면 그대로 둔다.
+        IpcRequest::Play if player.main_state.on_play => {}
+        IpcRequest::Pause if !player.main_state.on_play => {}
+        IpcRequest::Play | IpcRequest::Pause | IpcRequest::Toggle => player.toggle_pause(),
+        IpcRequest::Stop => player.stop(),
+        IpcRequest::Next => player.next(),
+        IpcRequest::Previous => player.previous(),
+        IpcRequest::Volume { volume } => player.set_volume(*volume),
+        IpcRequest::Shuffle { shuffle } => player.set_random_mode(*shuffle),
+        IpcRequest::Seek { offset_millis } => player.seek_by(*offset_millis),
+        IpcRequest::Queue => {
+            return IpcResponse::queue(TrackInfo::list(&player.main_state.music_list));
+        }
+        IpcRequest::Library => {
+            return match player.library() {
+                Ok(paths) => IpcResponse::library(paths),
+                Err(error) => IpcResponse::error(error.to_string()),
+            };
+        }
+        IpcRequest::PlayIndex { index } => {
+            if *index >= player.main_state.music_list.list.len() {
+                return IpcResponse::error("index out of range");
+            }
+
+            player.play_index(*index);
+        }
+        IpcRequest::Remove { index } => {
+            if !player.remove_music(*index) {
+                return IpcResponse::error("index out of range");
+            }
+        }
+        IpcRequest::Clear => player.clear_music_list(),
+        IpcRequest::Quit => {}
+    }
+
+    IpcResponse::ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn status_relay_forgets_closed_subscribers() {
+        let background_state = BackgroundState::new(false);
+        let status_relay = StatusRelay::start(&background_state);
+
+        let (event_sender, events) = mpsc::channel();
+        status_relay.subscribe(move |event| event_sender.send(event.clone()).is_ok());
+        let (closed_sender, closed_events) = mpsc::channel();
+        status_relay.subscribe(move |event| closed_sender.send(event.clone()).is_ok());
+        drop(closed_events);
+
+        background_state.publish(BackgroundStatusEvent::PauseChanged(true));
+
+        assert_eq!(
+            Ok(BackgroundStatusEvent::PauseChanged(true)),
+            events.recv_timeout(Duration::from_secs(5))
+        );
+        assert_eq!(1, status_relay.forwarders.lock().unwrap().len());
+    }
+}
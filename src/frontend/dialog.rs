@@ -14,7 +14,7 @@ pub fn open_directory_dialog() -> anyhow::Result<PathBuf> {
         }
     };
 
-    println!("Selected file: {:?}", path);
+    log::debug!("Selected file: {:?}", path);
 
     Ok(path)
 }
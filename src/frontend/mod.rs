@@ -1,277 +1,468 @@
 mod dialog;
 mod modal;
+mod remote;
+mod status;
+mod theme;
+
+use std::path::PathBuf;
+use std::sync::LazyLock;
+
+use iced::widget::{self, Column, button, checkbox, column, container, text, text_input, toggler};
+use iced::{Element, Length, Subscription, Task, Theme, advanced, alignment};
+use musica_core::backend::state::BackgroundStatusEvent;
+use musica_core::config;
+use musica_core::file::{self, FileInfo};
+use musica_core::player::Player;
+use musica_core::shortcut::{KeyPress, SEEK_STEP_MILLIS, ShortcutAction, VOLUME_STEP};
+use musica_core::sort::SortOrder;
+use musica_core::state::format_duration;
+
+use crate::album_art;
+use crate::cli::Cli;
+use crate::control;
+use crate::ipc::{IpcMessage, IpcReceiver, IpcRequest, IpcSender};
+use crate::search::SearchQuery;
 
-use std::sync::mpsc::Sender;
-use std::sync::{Arc, LazyLock, mpsc};
-use std::time::{Duration, Instant};
-
-use crate::backend::background_loop;
-use crate::backend::state::{BackgroundLoopEvent, BackgroundState};
-use crate::state::{MainState, Music, MusicList};
-use config::Config;
-use iced::widget::{self, Column, button, column, container, text, text_input, toggler};
-use iced::{Color, Element, Length, Subscription, Theme, advanced, alignment};
+static TEXT_INPUT_ID: LazyLock<widget::Id> = LazyLock::new(widget::Id::unique);
+static MUSIC_LIST_ID: LazyLock<widget::Id> = LazyLock::new(widget::Id::unique);
 
-use crate::{config, file};
+/// 이보다 넓은 창에서는 재생 목록에 아티스트/앨범/길이 열을 함께 표시
+const WIDE_LAYOUT_MIN_WIDTH: f32 = 600.0;
 
-static TEXT_INPUT_ID: LazyLock<widget::Id> = LazyLock::new(widget::Id::unique);
+/// 미니 플레이어 창 크기
+const MINI_MODE_SIZE: iced::Size = iced::Size::new(300.0, 120.0);
 
 pub struct MainApp {
-    main_state: MainState,
-    config_data: Config,
+    player: Player,
     show_setting_modal: bool,
-
-    background_event_sender: Sender<BackgroundLoopEvent>,
-    background_state: BackgroundState,
+    show_shortcut_modal: bool,
+    search_query: String,
+    music_directory_input: String, // 설정 화면에서 입력 중인 음악 폴더 경로 (제출해야 적용)
+    resume_position_min_input: String, // 설정 화면에서 입력 중인 곡별 재생 위치 기준 (분, 제출해야 적용)
+    is_mini_mode: bool,                // 항상 위에 떠 있는 작은 재생 막대로 표시 중인지 여부
+    theme: Theme,
+    system_theme_mode: iced::theme::Mode, // 시스템의 라이트/다크 설정
+    modifiers: iced::keyboard::Modifiers, // 현재 누르고 있는 Shift, Ctrl 등 (파일 끌어다 놓기에 사용)
+    ipc_receiver: IpcReceiver,            // 다른 실행, ctl 명령, MPRIS에서 보낸 요청
 }
 
 #[derive(Debug, Clone)]
 pub enum ForegroundEvent {
     ResumeOrPausePressed,
+    StopPressed,
+    StopAfterCurrentToggled(bool),
     NextPressed,
     PreviousPressed,
 
     DirectPlayMusic(usize),
+    JumpToCurrentMusic,
+    SeekBy(i64),
+
+    SearchQueryChanged(String),
+    SearchSubmitted,
+    SortOrderChanged(SortOrder),
+
+    MiniModeToggled,
 
     OpenSettingModal,
     CloseSettingModal,
     MusicDirectoryInputChanged(String),
+    MusicDirectorySubmitted,
     ChooseMusicDirectory,
 
+    ThemeChanged(String),
+    SystemThemeChanged(iced::theme::Mode),
     RandomToggled(bool),
     VolumeChanged(f32),
-
-    #[allow(dead_code)]
-    Tick(Instant),
+    MuteToggled,
+    ResumeSessionToggled(bool),
+    StartPausedToggled(bool),
+    ResumePositionMinInputChanged(String),
+    ResumePositionMinSubmitted,
+
+    OpenShortcutModal,
+    CloseShortcutModal,
+    KeyPressed(KeyPress),
+    ModifiersChanged(iced::keyboard::Modifiers),
+
+    BackgroundStatus(BackgroundStatusEvent),
+    AlbumArtLoaded {
+        file_path: PathBuf,
+        thumbnail: Option<PathBuf>,
+    },
+
+    WindowResized(iced::Size),
+    WindowMoved(iced::Point),
+    FileDropped(PathBuf),
+    IpcRequested(IpcMessage),
+    CloseRequested,
 }
 
 impl MainApp {
-    pub fn new() -> Self {
-        let config_path = config::get_config_path();
-        let config_data = config::read_config_if_exists(config_path).unwrap_or_default();
-
-        let (sender, receiver) = mpsc::channel::<BackgroundLoopEvent>();
-
-        let background_state = BackgroundState {
-            current_music_index: Default::default(),
-            current_index: Default::default(),
-            is_random_mode: Arc::new(config_data.is_random.into()),
-            is_paused: Arc::new(false.into()),
-        };
-
+    pub fn new(cli: &Cli, ipc_sender: IpcSender, ipc_receiver: IpcReceiver) -> Self {
         let mut app = Self {
-            main_state: MainState {
-                title: "no music".into(),
-                music_list: MusicList::default(),
-                on_play: true,
-            },
-            config_data,
+            player: control::start_player(cli, ipc_sender),
             show_setting_modal: false,
-            background_state,
-            background_event_sender: sender,
+            show_shortcut_modal: false,
+            search_query: String::new(),
+            music_directory_input: String::new(),
+            resume_position_min_input: String::new(),
+            is_mini_mode: false,
+            theme: Theme::Dracula,
+            system_theme_mode: iced::theme::Mode::None,
+            modifiers: iced::keyboard::Modifiers::default(),
+            ipc_receiver,
         };
 
-        app.update_music_list_from_config();
-
-        app.config_data.volume = Config::normalize_volume(app.config_data.volume);
-
-        let music_list = app.main_state.music_list.clone();
-
-        background_loop(
-            receiver,
-            app.background_state.clone(),
-            music_list,
-            app.config_data.volume,
-        );
-
-        // 백그라운드 스레드가 OutputStream/Sink를 생성한 뒤 첫 곡을 자동 재생하도록 트리거
-        app.background_event_sender
-            .send(BackgroundLoopEvent::StartUp)
-            .unwrap();
+        app.update_theme();
 
         app
     }
+    /// 앱을 만들고 시스템 테마를 조회한다. ("System" 테마가 라이트/다크를 따라가도록)
+    pub fn boot(
+        cli: &Cli,
+        ipc_sender: IpcSender,
+        ipc_receiver: IpcReceiver,
+    ) -> (Self, Task<ForegroundEvent>) {
+        // 원본이 없어진 앨범 커버 캐시는 화면과 상관없이 별도 스레드에서 정리한다.
+        std::thread::spawn(|| {
+            if let Err(err) = album_art::prune_cache(&album_art::get_album_art_cache_path()) {
+                log::error!("Failed to prune album art cache: {:?}", err);
+            }
+        });
+
+        (
+            Self::new(cli, ipc_sender, ipc_receiver),
+            iced::system::theme().map(ForegroundEvent::SystemThemeChanged),
+        )
+    }
+
     pub fn theme(&self) -> iced::Theme {
-        iced::Theme::Dracula
+        self.theme.clone()
     }
 
-    pub fn update(&mut self, message: ForegroundEvent) {
+    pub fn update(&mut self, message: ForegroundEvent) -> Task<ForegroundEvent> {
         match message {
             ForegroundEvent::ResumeOrPausePressed => {
-                if self.main_state.on_play {
-                    if let Err(error) = self
-                        .background_event_sender
-                        .send(BackgroundLoopEvent::Pause)
-                    {
-                        println!("Failed to send event: {:?}", error);
-                    }
-
-                    self.main_state.on_play = false;
-                } else {
-                    if let Err(error) = self
-                        .background_event_sender
-                        .send(BackgroundLoopEvent::Resume)
-                    {
-                        println!("Failed to send event: {:?}", error);
-                    }
-
-                    self.main_state.on_play = true;
-                }
+                self.player.toggle_pause();
+            }
+            ForegroundEvent::StopPressed => {
+                self.player.stop();
+            }
+            ForegroundEvent::StopAfterCurrentToggled(flag) => {
+                self.player.set_stop_after_current(flag);
             }
             ForegroundEvent::NextPressed => {
-                if let Err(error) = self.background_event_sender.send(BackgroundLoopEvent::Next) {
-                    println!("Failed to send event: {:?}", error);
-                }
+                self.player.next();
             }
             ForegroundEvent::PreviousPressed => {
-                if let Err(error) = self
-                    .background_event_sender
-                    .send(BackgroundLoopEvent::Previous)
-                {
-                    println!("Failed to send event: {:?}", error);
-                }
+                self.player.previous();
             }
             ForegroundEvent::OpenSettingModal => {
+                self.show_shortcut_modal = false;
                 self.show_setting_modal = true;
+                self.music_directory_input = self
+                    .player
+                    .config_data
+                    .directory_path
+                    .to_string_lossy()
+                    .into_owned();
+                self.resume_position_min_input =
+                    (self.player.config_data.resume_position_min_secs / 60).to_string();
             }
             ForegroundEvent::CloseSettingModal => {
                 self.show_setting_modal = false;
+                self.apply_music_directory_input();
+                self.apply_resume_position_min_input();
             }
             ForegroundEvent::ChooseMusicDirectory => {
                 let path = dialog::open_directory_dialog();
 
                 if let Ok(path) = path {
-                    self.config_data.directory_path = path;
-
-                    if let Err(err) = self
-                        .config_data
-                        .update_config_if_exists(config::get_config_path())
-                    {
-                        println!("Failed to update config: {:?}", err);
-                    }
-
-                    self.update_music_list_from_config();
+                    self.music_directory_input = path.to_string_lossy().into_owned();
+                    self.player.set_music_directory(path);
                 }
             }
             ForegroundEvent::MusicDirectoryInputChanged(text) => {
-                self.config_data.directory_path = text.clone().into();
+                self.music_directory_input = text;
+            }
+            ForegroundEvent::MusicDirectorySubmitted => {
+                self.apply_music_directory_input();
+            }
+            ForegroundEvent::BackgroundStatus(status) => {
+                let previous_music_index = self.player.main_state.current_music_index;
 
-                if let Err(err) = self
-                    .config_data
-                    .update_config_if_exists(config::get_config_path())
-                {
-                    println!("Failed to update config: {:?}", err);
-                }
+                self.player.handle_status(&status);
 
-                self.update_music_list_from_config();
-            }
-            ForegroundEvent::Tick(_) => {
-                // 백엔드의 실제 일시정지 상태를 프론트엔드 on_play와 동기화
-                let is_paused = self
-                    .background_state
-                    .is_paused
-                    .load(std::sync::atomic::Ordering::Acquire);
-                self.main_state.on_play = !is_paused;
+                if let BackgroundStatusEvent::MusicChanged(index) = status {
+                    let load_album_art = match self.player.main_state.music_list.list.get(index) {
+                        Some(music) => self::load_album_art(music.file_path.clone()),
+                        None => Task::none(),
+                    };
 
-                let current_music_index = self
-                    .background_state
-                    .current_music_index
-                    .load(std::sync::atomic::Ordering::Acquire);
+                    // 곡이 바뀌면 목록을 재생 중인 곡 위치로 스크롤
+                    if previous_music_index != index {
+                        return Task::batch([load_album_art, self.scroll_to_current_music()]);
+                    }
 
-                if self.main_state.music_list.list.is_empty() {
-                    return;
+                    return load_album_art;
+                }
+            }
+            ForegroundEvent::AlbumArtLoaded {
+                file_path,
+                thumbnail,
+            } => {
+                // 읽는 사이에 곡이 바뀌었으면 무시
+                let is_current_music = self
+                    .player
+                    .main_state
+                    .music_list
+                    .list
+                    .get(self.player.main_state.current_music_index)
+                    .is_some_and(|music| music.file_path == file_path);
+
+                if is_current_music {
+                    self.player.main_state.album_art_path = thumbnail;
                 }
+            }
+            ForegroundEvent::ThemeChanged(theme) => {
+                self.player.config_data.theme = theme;
 
-                let current_music = &self.main_state.music_list.list[current_music_index];
-                self.main_state.title = current_music.title.clone();
+                self.player.save_config();
+
+                self.update_theme();
+            }
+            ForegroundEvent::SystemThemeChanged(mode) => {
+                self.system_theme_mode = mode;
+                self.update_theme();
             }
             ForegroundEvent::RandomToggled(flag) => {
-                self.config_data.is_random = flag;
-
-                if let Err(err) = self
-                    .config_data
-                    .update_config_if_exists(config::get_config_path())
+                self.player.set_random_mode(flag);
+            }
+            ForegroundEvent::VolumeChanged(volume) => {
+                self.player.set_volume(volume);
+            }
+            ForegroundEvent::SearchQueryChanged(query) => {
+                self.search_query = query;
+            }
+            ForegroundEvent::SearchSubmitted => {
+                // 검색 결과의 첫 곡을 재생
+                let query = SearchQuery::new(&self.search_query);
+
+                if let Some(index) = self
+                    .player
+                    .main_state
+                    .music_list
+                    .list
+                    .iter()
+                    .position(|music| query.matches(music))
                 {
-                    println!("Failed to update config: {:?}", err);
+                    return self.update(ForegroundEvent::DirectPlayMusic(index));
+                }
+            }
+            ForegroundEvent::JumpToCurrentMusic => {
+                // 재생 중인 곡이 검색 결과에 없으면 검색어를 지운다.
+                let is_current_music_visible = self
+                    .player
+                    .main_state
+                    .music_list
+                    .list
+                    .get(self.player.main_state.current_music_index)
+                    .is_some_and(|music| SearchQuery::new(&self.search_query).matches(music));
+
+                if !is_current_music_visible {
+                    self.search_query.clear();
                 }
 
-                self.background_state
-                    .is_random_mode
-                    .store(flag, std::sync::atomic::Ordering::Relaxed);
+                return self.scroll_to_current_music();
             }
-            ForegroundEvent::VolumeChanged(volume) => {
-                let volume = Config::normalize_volume(volume);
-                self.config_data.volume = volume;
+            ForegroundEvent::SortOrderChanged(sort_order) => {
+                self.player.set_sort_order(sort_order);
+            }
+            ForegroundEvent::SeekBy(offset_millis) => {
+                self.player.seek_by(offset_millis);
+            }
+            ForegroundEvent::MuteToggled => {
+                self.player.toggle_mute();
+            }
+            ForegroundEvent::DirectPlayMusic(index) => {
+                self.player.play_index(index);
+            }
+            ForegroundEvent::ResumeSessionToggled(flag) => {
+                self.player.config_data.resume_session = flag;
 
-                if let Err(err) = self
-                    .config_data
-                    .update_config_if_exists(config::get_config_path())
+                self.player.save_config();
+            }
+            ForegroundEvent::StartPausedToggled(flag) => {
+                self.player.config_data.start_paused = flag;
+
+                self.player.save_config();
+            }
+            ForegroundEvent::ResumePositionMinInputChanged(text) => {
+                // 숫자가 아닌 입력은 무시
+                if text.chars().all(|c| c.is_ascii_digit()) {
+                    self.resume_position_min_input = text;
+                }
+            }
+            ForegroundEvent::ResumePositionMinSubmitted => {
+                self.apply_resume_position_min_input();
+            }
+            ForegroundEvent::OpenShortcutModal => {
+                if self.show_setting_modal {
+                    self.show_setting_modal = false;
+                    self.apply_music_directory_input();
+                    self.apply_resume_position_min_input();
+                }
+                self.show_shortcut_modal = true;
+            }
+            ForegroundEvent::CloseShortcutModal => {
+                self.show_shortcut_modal = false;
+            }
+            ForegroundEvent::KeyPressed(key_press) => {
+                // Esc는 바인딩과 상관없이 열린 창을 닫는다.
+                if key_press.key == "escape"
+                    && (self.show_setting_modal || self.show_shortcut_modal)
                 {
-                    println!("Failed to update config: {:?}", err);
+                    self.show_shortcut_modal = false;
+
+                    if self.show_setting_modal {
+                        return self.update(ForegroundEvent::CloseSettingModal);
+                    }
+
+                    return Task::none();
                 }
 
-                if let Err(error) = self
-                    .background_event_sender
-                    .send(BackgroundLoopEvent::VolumeChanged(volume))
-                {
-                    println!("Failed to send event: {:?}", error);
+                if let Some(action) = self.player.config_data.key_bindings.action_for(&key_press) {
+                    return self.update(self.shortcut_event(action));
                 }
             }
-            ForegroundEvent::DirectPlayMusic(index) => {
-                if let Err(error) = self
-                    .background_event_sender
-                    .send(BackgroundLoopEvent::DirectPlayMusic(index))
-                {
-                    println!("Failed to send event: {:?}", error);
+            ForegroundEvent::MiniModeToggled => {
+                self.is_mini_mode = !self.is_mini_mode;
+
+                // 미니 모드에서는 창을 작게 만들어 항상 위에 띄우고, 돌아올 때 원래 크기로 복원
+                let (size, min_size, level) = if self.is_mini_mode {
+                    (
+                        MINI_MODE_SIZE,
+                        MINI_MODE_SIZE,
+                        iced::window::Level::AlwaysOnTop,
+                    )
+                } else {
+                    (
+                        iced::Size::new(
+                            self.player.config_data.window.width,
+                            self.player.config_data.window.height,
+                        ),
+                        iced::Size::new(
+                            config::WindowGeometry::MIN_WIDTH,
+                            config::WindowGeometry::MIN_HEIGHT,
+                        ),
+                        iced::window::Level::Normal,
+                    )
+                };
+
+                return iced::window::latest().and_then(move |id| {
+                    Task::batch([
+                        iced::window::set_min_size(id, Some(min_size)),
+                        iced::window::resize(id, size),
+                        iced::window::set_level(id, level),
+                    ])
+                });
+            }
+            ForegroundEvent::WindowResized(size) => {
+                // 미니 모드의 크기는 기억하지 않는다.
+                if self.is_mini_mode {
+                    return Task::none();
                 }
+
+                // 창 크기는 종료할 때 한 번에 저장한다.
+                self.player.config_data.window.width = size.width;
+                self.player.config_data.window.height = size.height;
+            }
+            ForegroundEvent::WindowMoved(position) => {
+                self.player.config_data.window.x = Some(position.x);
+                self.player.config_data.window.y = Some(position.y);
+            }
+            ForegroundEvent::ModifiersChanged(modifiers) => {
+                self.modifiers = modifiers;
+            }
+            ForegroundEvent::FileDropped(path) => {
+                self.drop_file(path);
+            }
+            ForegroundEvent::IpcRequested(message) => {
+                let response = control::handle_request(&mut self.player, &message.request);
+                message.reply(response);
+
+                match message.request {
+                    // 다시 실행하면 이미 떠 있는 창을 앞으로 가져온다.
+                    IpcRequest::Open { .. } => {
+                        return iced::window::latest().and_then(iced::window::gain_focus);
+                    }
+                    IpcRequest::Quit => return self.update(ForegroundEvent::CloseRequested),
+                    _ => {}
+                }
+            }
+            ForegroundEvent::CloseRequested => {
+                self.player.shutdown();
+
+                return iced::exit();
             }
         }
+
+        Task::none()
     }
 
     pub fn view(&self) -> Element<'_, ForegroundEvent> {
+        if self.is_mini_mode {
+            return self.mini_view();
+        }
+
         let content = container(
             column!(
                 container(
                     container(column!(
-                        container(self.setting_button()).padding(0),
-                        container(
-                            text(self.main_state.title.as_str())
-                                .size(15)
-                                .height(Length::Fill)
-                                .shaping(advanced::text::Shaping::Advanced)
-                        )
+                        widget::row![self.setting_button(), self.mini_mode_button("mini")]
+                            .spacing(5),
+                        widget::row![
+                            self.album_art_view(),
+                            container(
+                                text(self.player.main_state.title.as_str())
+                                    .size(15)
+                                    .shaping(advanced::text::Shaping::Advanced)
+                            )
+                            .align_x(alignment::Horizontal::Center)
+                            .width(Length::Fill),
+                        ]
+                        .spacing(10)
                         .padding(10)
-                        .align_x(alignment::Horizontal::Center)
-                        .width(Length::Fill),
+                        .height(Length::Fill)
+                        .align_y(alignment::Vertical::Center),
+                        self.position_view(),
+                        self.output_status_view(),
                         container(self.button_view())
                             .padding(5)
                             .align_x(alignment::Horizontal::Center)
                             .width(Length::Fill),
+                        container(
+                            checkbox(self.player.main_state.stop_after_current)
+                                .label("Stop after current")
+                                .on_toggle(ForegroundEvent::StopAfterCurrentToggled)
+                                .size(12)
+                                .text_size(11),
+                        )
+                        .align_x(alignment::Horizontal::Center)
+                        .width(Length::Fill),
                         container(self.volume_view())
                             .padding(5)
                             .align_x(alignment::Horizontal::Center)
                             .width(Length::Fill),
                     ),)
-                    .style(|_: &Theme| {
-                        container::Style {
-                            text_color: Some(Color::BLACK),
-                            border: iced::Border {
-                                width: 1.0,
-                                radius: 10.0.into(),
-                                ..Default::default()
-                            },
-                            background: Some(iced::Background::Color(Color::from_rgb8(
-                                0x44, 0x47, 0x5a,
-                            ))),
-                            ..Default::default()
-                        }
-                    })
+                    .style(theme::panel)
                     .padding(10),
                 )
                 .width(Length::Fill)
-                .height(Length::Fixed(195_f32))
+                .height(Length::Fixed(280_f32))
                 .padding(10),
-                container(self.items_list_view())
+                container(column![self.search_view(), self.items_list_view()].spacing(5))
                     .height(Length::Fill)
                     .padding(10),
             )
@@ -287,25 +478,118 @@ impl MainApp {
             let modal_view = self.setting_modal_view();
 
             modal::create_modal(content, modal_view, ForegroundEvent::CloseSettingModal)
+        } else if self.show_shortcut_modal {
+            let modal_view = self.shortcut_modal_view();
+
+            modal::create_modal(content, modal_view, ForegroundEvent::CloseShortcutModal)
         } else {
             content
         }
     }
 
     pub fn subscription(&self) -> iced::Subscription<ForegroundEvent> {
-        let tick = iced::time::every(Duration::from_millis(500)).map(ForegroundEvent::Tick);
+        let background_status = status::subscription(self.player.background_state())
+            .map(ForegroundEvent::BackgroundStatus);
+
+        let close_requests =
+            iced::window::close_requests().map(|_| ForegroundEvent::CloseRequested);
+
+        // 텍스트 입력 등 위젯이 처리하지 않은 키 입력만 단축키로 사용
+        let key_presses = iced::keyboard::listen().filter_map(|event| match event {
+            iced::keyboard::Event::KeyPressed {
+                modified_key,
+                physical_key,
+                modifiers,
+                ..
+            } => key_press(modified_key, physical_key, modifiers).map(ForegroundEvent::KeyPressed),
+            iced::keyboard::Event::ModifiersChanged(modifiers) => {
+                Some(ForegroundEvent::ModifiersChanged(modifiers))
+            }
+            _ => None,
+        });
 
-        Subscription::batch(vec![tick])
-    }
-}
+        let system_theme_changes =
+            iced::system::theme_changes().map(ForegroundEvent::SystemThemeChanged);
+
+        let window_events = iced::window::events().filter_map(|(_, event)| match event {
+            iced::window::Event::Resized(size) => Some(ForegroundEvent::WindowResized(size)),
+            iced::window::Event::Moved(position) => Some(ForegroundEvent::WindowMoved(position)),
+            iced::window::Event::FileDropped(path) => Some(ForegroundEvent::FileDropped(path)),
+            _ => None,
+        });
 
-impl Default for MainApp {
-    fn default() -> Self {
-        Self::new()
+        let ipc_requests =
+            remote::subscription(&self.ipc_receiver).map(ForegroundEvent::IpcRequested);
+
+        Subscription::batch(vec![
+            background_status,
+            close_requests,
+            key_presses,
+            window_events,
+            system_theme_changes,
+            ipc_requests,
+        ])
     }
 }
 
 impl MainApp {
+    fn mini_view(&self) -> Element<'_, ForegroundEvent> {
+        let progress = match self.player.main_state.duration {
+            Some(duration) if !duration.is_zero() && !self.player.main_state.is_stopped => {
+                self.player.main_state.position.as_secs_f32() / duration.as_secs_f32()
+            }
+            _ => 0.0,
+        };
+
+        container(
+            column![
+                widget::row![
+                    text(self.player.main_state.title.as_str())
+                        .size(12)
+                        .shaping(advanced::text::Shaping::Advanced)
+                        .wrapping(text::Wrapping::None)
+                        .width(Length::Fill),
+                    self.mini_mode_button("full"),
+                ]
+                .spacing(5)
+                .align_y(iced::Alignment::Center),
+                container(self.button_view())
+                    .align_x(alignment::Horizontal::Center)
+                    .width(Length::Fill),
+                widget::progress_bar(0.0..=1.0, progress).girth(4),
+            ]
+            .spacing(6),
+        )
+        .padding(8)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+    }
+
+    /// 앨범 커버 (없으면 같은 크기의 빈 칸)
+    fn album_art_view(&self) -> Element<'_, ForegroundEvent> {
+        const ALBUM_ART_SIZE: f32 = 64.0;
+
+        match &self.player.main_state.album_art_path {
+            Some(path) => widget::image(widget::image::Handle::from_path(path))
+                .width(ALBUM_ART_SIZE)
+                .height(ALBUM_ART_SIZE)
+                .into(),
+            None => container(text("♪").size(28).style(theme::dimmed_text))
+                .center(ALBUM_ART_SIZE)
+                .style(theme::panel)
+                .into(),
+        }
+    }
+
+    fn mini_mode_button(&self, label: &'static str) -> Element<'static, ForegroundEvent> {
+        button(text(label).size(12))
+            .on_press(ForegroundEvent::MiniModeToggled)
+            .padding(3)
+            .style(theme::pill_button)
+            .into()
+    }
+
     pub fn setting_button(&self) -> Element<'static, ForegroundEvent> {
         let setting_button = button(
             text("setting")
@@ -315,16 +599,7 @@ impl MainApp {
         )
         .on_press(ForegroundEvent::OpenSettingModal)
         .padding(3)
-        .style(|_, _| iced::widget::button::Style {
-            background: Some(iced::Background::Color(Color::from_rgba8(
-                0xff, 0xff, 0xff, 0.5,
-            ))),
-            border: iced::Border {
-                radius: 10.0.into(),
-                ..Default::default()
-            },
-            ..Default::default()
-        });
+        .style(theme::pill_button);
 
         setting_button.into()
     }
@@ -335,32 +610,184 @@ impl MainApp {
             .align_x(iced::Alignment::Start)
             .width(Length::Fill);
 
-        for (i, value) in self.main_state.music_list.list.iter().enumerate() {
-            let text_widget = text(value.title.as_str())
+        let query = SearchQuery::new(&self.search_query);
+        let is_wide_layout = self.is_wide_layout();
+
+        // 검색어로 거르더라도 DirectPlayMusic에는 원래 목록의 인덱스를 사용
+        for (i, value) in self
+            .player
+            .main_state
+            .music_list
+            .list
+            .iter()
+            .enumerate()
+            .filter(|(_, music)| query.matches(music))
+        {
+            let is_current_music = i == self.player.main_state.current_music_index;
+
+            // 재생할 수 없는 곡은 흐리게 표시
+            let is_broken = value.error.is_some();
+
+            let title = if is_current_music {
+                format!("▶ {}", value.title)
+            } else {
+                value.title.clone()
+            };
+
+            let title_widget = text(title)
                 .size(12)
                 .shaping(advanced::text::Shaping::Advanced)
-                .color(Color::WHITE);
+                .style(move |theme: &Theme| theme::music_text(theme, is_broken))
+                .width(Length::Fill);
+
+            // 이어서 재생할 위치가 있는 긴 곡은 힌트를 함께 표시
+            let text_widget: Element<'_, ForegroundEvent> = match value.resume_position {
+                Some(position) => widget::row![
+                    title_widget,
+                    text(format!("resume at {}", format_duration(position)))
+                        .size(10)
+                        .style(theme::accent_text),
+                ]
+                .spacing(5)
+                .align_y(iced::Alignment::Center)
+                .into(),
+                None => title_widget.into(),
+            };
+
+            let text_widget: Element<'_, ForegroundEvent> = if is_wide_layout {
+                let column_text = |value: String| {
+                    text(value)
+                        .size(12)
+                        .shaping(advanced::text::Shaping::Advanced)
+                        .style(move |theme: &Theme| theme::music_text(theme, is_broken))
+                };
+
+                widget::row![
+                    container(text_widget).width(Length::FillPortion(3)),
+                    column_text(value.metadata.artist.clone().unwrap_or_default())
+                        .width(Length::FillPortion(2)),
+                    column_text(value.metadata.album.clone().unwrap_or_default())
+                        .width(Length::FillPortion(2)),
+                    column_text(
+                        value
+                            .metadata
+                            .duration
+                            .map(format_duration)
+                            .unwrap_or_default()
+                    )
+                    .width(Length::Fixed(40.0)),
+                ]
+                .spacing(10)
+                .align_y(iced::Alignment::Center)
+                .into()
+            } else {
+                text_widget
+            };
 
             let button_widget = button(text_widget)
                 .on_press_with(move || ForegroundEvent::DirectPlayMusic(i))
                 .padding(5)
-                .style(|_, _| iced::widget::button::Style {
-                    background: Some(iced::Background::Color(Color::from_rgba8(
-                        0x0, 0x0, 0x0, 0.5,
-                    ))),
-                    border: iced::Border {
-                        radius: 0.0.into(),
-                        width: 0.1,
-                        ..Default::default()
-                    },
-                    ..Default::default()
-                })
+                .style(move |theme: &Theme, _| theme::music_row(theme, is_current_music))
                 .width(Length::Fill);
 
-            column = column.push(button_widget);
+            if let Some(error) = &value.error {
+                let tooltip_widget = widget::tooltip(
+                    button_widget,
+                    container(text(error.as_str()).size(11))
+                        .padding(5)
+                        .style(container::rounded_box),
+                    widget::tooltip::Position::FollowCursor,
+                );
+
+                column = column.push(tooltip_widget);
+            } else {
+                column = column.push(button_widget);
+            }
+        }
+
+        let list = widget::scrollable(container(column))
+            .id(MUSIC_LIST_ID.clone())
+            .width(Length::Fill);
+
+        if !is_wide_layout {
+            return list.into();
+        }
+
+        let header_text = |label| text(label).size(11).style(theme::dimmed_text);
+
+        let header = widget::row![
+            header_text("Title").width(Length::FillPortion(3)),
+            header_text("Artist").width(Length::FillPortion(2)),
+            header_text("Album").width(Length::FillPortion(2)),
+            header_text("Time").width(Length::Fixed(40.0)),
+        ]
+        .spacing(10)
+        .padding([0, 5]);
+
+        column![header, list].spacing(5).into()
+    }
+
+    /// 창이 충분히 넓으면 재생 목록을 여러 열로 표시한다.
+    fn is_wide_layout(&self) -> bool {
+        self.player.config_data.window.width >= WIDE_LAYOUT_MIN_WIDTH
+    }
+
+    fn search_view(&self) -> Element<'_, ForegroundEvent> {
+        widget::row![
+            text_input("Search", &self.search_query)
+                .on_input(ForegroundEvent::SearchQueryChanged)
+                .on_submit(ForegroundEvent::SearchSubmitted)
+                .padding(5)
+                .size(12)
+                .width(Length::Fill),
+            widget::pick_list(
+                SortOrder::ALL,
+                Some(self.player.config_data.sort_order),
+                ForegroundEvent::SortOrderChanged,
+            )
+            .padding(5)
+            .text_size(12),
+            button(text("Now").size(12))
+                .on_press(ForegroundEvent::JumpToCurrentMusic)
+                .padding(5),
+        ]
+        .spacing(5)
+        .width(Length::Fill)
+        .into()
+    }
+
+    fn position_view(&self) -> Element<'static, ForegroundEvent> {
+        if self.player.main_state.is_stopped {
+            return container(text("stopped").size(11))
+                .align_x(alignment::Horizontal::Center)
+                .width(Length::Fill)
+                .into();
         }
 
-        widget::scrollable(container(column)).width(300).into()
+        let position_text = match self.player.main_state.duration {
+            Some(duration) => format!(
+                "{} / {}",
+                format_duration(self.player.main_state.position),
+                format_duration(duration)
+            ),
+            None => format_duration(self.player.main_state.position),
+        };
+
+        container(text(position_text).size(11))
+            .align_x(alignment::Horizontal::Center)
+            .width(Length::Fill)
+            .into()
+    }
+
+    fn output_status_view(&self) -> Element<'static, ForegroundEvent> {
+        if self.player.main_state.is_output_available {
+            return widget::Space::new().into();
+        }
+
+        container(text("no audio output").size(11).style(text::danger))
+            .align_x(alignment::Horizontal::Center)
+            .width(Length::Fill)
+            .into()
     }
 
     fn button_view(&self) -> Element<'static, ForegroundEvent> {
@@ -384,7 +811,11 @@ impl MainApp {
         .width(Length::Fixed(50_f32))
         .height(Length::Fixed(50_f32));
 
-        let resume_or_pause_button_text = if self.main_state.on_play { "||" } else { ">>" };
+        let resume_or_pause_button_text = if self.player.main_state.on_play {
+            "||"
+        } else {
+            ">>"
+        };
 
         let resume_or_pause_button = button(
             text(resume_or_pause_button_text)
@@ -396,25 +827,45 @@ impl MainApp {
         .width(Length::Fixed(50_f32))
         .height(Length::Fixed(50_f32));
 
-        widget::row!(prev_button, resume_or_pause_button, next_button,)
-            .spacing(10)
-            .into()
+        let stop_button = button(
+            text("[]")
+                .align_x(alignment::Horizontal::Center)
+                .align_y(alignment::Vertical::Center),
+        )
+        .on_press(ForegroundEvent::StopPressed)
+        .padding(10)
+        .width(Length::Fixed(50_f32))
+        .height(Length::Fixed(50_f32));
+
+        widget::row!(
+            prev_button,
+            resume_or_pause_button,
+            stop_button,
+            next_button,
+        )
+        .spacing(10)
+        .into()
     }
 
     fn volume_view(&self) -> Element<'_, ForegroundEvent> {
-        let volume_percent = (self.config_data.volume * 100.0).round() as u8;
+        let volume_text = if self.player.main_state.is_muted {
+            "mute".to_string()
+        } else {
+            format!(
+                "{}%",
+                (self.player.config_data.volume * 100.0).round() as u8
+            )
+        };
 
         widget::row![
             text("Volume").size(12),
             widget::slider(
                 0.0..=1.0,
-                self.config_data.volume,
+                self.player.config_data.volume,
                 ForegroundEvent::VolumeChanged,
             )
             .step(0.01),
-            text(format!("{volume_percent}%"))
-                .size(12)
-                .width(Length::Fixed(36.0)),
+            text(volume_text).size(12).width(Length::Fixed(36.0)),
         ]
         .spacing(8)
         .align_y(iced::Alignment::Center)
@@ -424,35 +875,198 @@ impl MainApp {
 }
 
 impl MainApp {
-    fn update_music_list_from_config(&mut self) {
-        let music_directory_path = self.config_data.directory_path.clone();
-
-        if let Ok(file_info_list) = file::read_file_list(&music_directory_path) {
-            self.main_state.music_list.list = file_info_list
-                .iter()
-                .filter(|x| x.is_music_file())
-                .map(|x| Music {
-                    title: x.filename.clone(),
-                    file_path: x.filepath.clone(),
-                })
-                .collect();
+    /// 단축키 동작을 버튼/설정과 같은 이벤트로 바꾼다.
+    fn shortcut_event(&self, action: ShortcutAction) -> ForegroundEvent {
+        match action {
+            ShortcutAction::PlayPause => ForegroundEvent::ResumeOrPausePressed,
+            ShortcutAction::Next => ForegroundEvent::NextPressed,
+            ShortcutAction::Previous => ForegroundEvent::PreviousPressed,
+            ShortcutAction::SeekForward => ForegroundEvent::SeekBy(SEEK_STEP_MILLIS),
+            ShortcutAction::SeekBackward => ForegroundEvent::SeekBy(-SEEK_STEP_MILLIS),
+            ShortcutAction::VolumeUp => {
+                ForegroundEvent::VolumeChanged(self.player.config_data.volume + VOLUME_STEP)
+            }
+            ShortcutAction::VolumeDown => {
+                ForegroundEvent::VolumeChanged(self.player.config_data.volume - VOLUME_STEP)
+            }
+            ShortcutAction::Mute => ForegroundEvent::MuteToggled,
+            ShortcutAction::ToggleShuffle => {
+                ForegroundEvent::RandomToggled(!self.player.config_data.is_random)
+            }
+            ShortcutAction::OpenSettings => ForegroundEvent::OpenSettingModal,
+            ShortcutAction::ShowShortcuts => ForegroundEvent::OpenShortcutModal,
         }
     }
+
+    fn update_theme(&mut self) {
+        self.theme = theme::resolve_theme(
+            &self.player.config_data.theme,
+            self.system_theme_mode,
+            &self.player.config_data.custom_colors,
+        );
+    }
+
+    /// 입력한 음악 폴더 경로를 적용한다. (Enter를 누르거나 설정 화면을 닫을 때)
+    /// 입력하는 도중의 경로("/", "/home" 등)로 목록을 바꾸면 재생이 멈추므로 입력할 때마다 적용하지 않는다.
+    fn apply_music_directory_input(&mut self) {
+        let path = PathBuf::from(self.music_directory_input.trim());
+
+        if path.is_dir() && path != self.player.config_data.directory_path {
+            self.player.set_music_directory(path);
+        }
+    }
+
+    /// 입력한 곡별 재생 위치 기준(분)을 적용한다. (Enter를 누르거나 설정 화면을 닫을 때, 비우면 0분)
+    fn apply_resume_position_min_input(&mut self) {
+        let minutes = match self.resume_position_min_input.trim() {
+            "" => 0,
+            text => text
+                .parse::<u64>()
+                .unwrap_or(self.player.config_data.resume_position_min_secs / 60),
+        };
+        self.resume_position_min_input = minutes.to_string();
+
+        let secs = minutes.saturating_mul(60);
+        if secs != self.player.config_data.resume_position_min_secs {
+            self.player.set_resume_position_min_secs(secs);
+        }
+    }
+
+    /// 창에 끌어다 놓은 파일이나 폴더를 재생 목록에 넣는다.
+    /// - 폴더: 안의 음악 파일을 목록에 추가 (Shift를 누르고 있으면 음악 폴더를 바꿈)
+    /// - 파일: 목록에 추가하고, 멈춰 있거나 Shift를 누르고 있으면 바로 재생
+    fn drop_file(&mut self, path: PathBuf) {
+        if path.is_dir() {
+            if self.modifiers.shift() {
+                self.music_directory_input = path.to_string_lossy().into_owned();
+                self.player.set_music_directory(path);
+                return;
+            }
+
+            match file::read_file_list(&path) {
+                Ok(file_info_list) => {
+                    self.player.add_music_files(&file_info_list);
+                    self.player.send_music_list_to_background();
+                }
+                Err(err) => log::error!("Failed to read dropped directory: {:?}", err),
+            }
+            return;
+        }
+
+        let file_info = match FileInfo::from_path(&path) {
+            Ok(file_info) => file_info,
+            Err(err) => {
+                log::error!("Failed to read dropped file: {:?}", err);
+                return;
+            }
+        };
+
+        self.player
+            .add_music_files(std::slice::from_ref(&file_info));
+        self.player.send_music_list_to_background();
+
+        if file_info.is_music_file()
+            && (self.modifiers.shift() || self.player.main_state.is_stopped)
+        {
+            self.player.play_file(&file_info.filepath);
+        }
+    }
+
+    /// 재생 중인 곡이 보이도록 목록을 스크롤한다.
+    /// 행 높이가 거의 같으므로 보이는 행 중의 순서를 비율로 바꿔 사용한다.
+    fn scroll_to_current_music(&self) -> Task<ForegroundEvent> {
+        let query = SearchQuery::new(&self.search_query);
+
+        let visible_indices = self
+            .player
+            .main_state
+            .music_list
+            .list
+            .iter()
+            .enumerate()
+            .filter(|(_, music)| query.matches(music))
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+
+        let Some(row) = visible_indices
+            .iter()
+            .position(|&i| i == self.player.main_state.current_music_index)
+        else {
+            return Task::none();
+        };
+
+        let y = if visible_indices.len() > 1 {
+            row as f32 / (visible_indices.len() - 1) as f32
+        } else {
+            0.0
+        };
+
+        widget::operation::snap_to(
+            MUSIC_LIST_ID.clone(),
+            widget::operation::RelativeOffset {
+                x: None,
+                y: Some(y),
+            },
+        )
+    }
 }
 
 impl MainApp {
     fn setting_modal_view(&self) -> Element<'_, ForegroundEvent> {
-        let toggler = toggler(self.config_data.is_random)
+        let random_toggler = toggler(self.player.config_data.is_random)
             .label("Random Mode")
             .on_toggle(ForegroundEvent::RandomToggled)
             .spacing(15);
 
-        let directory_path = self.config_data.directory_path.clone();
-        let directory_path_text = directory_path.as_os_str().to_str().unwrap_or_default();
+        let theme_pick_list = widget::row![
+            text("Theme").size(12),
+            widget::pick_list(
+                theme::theme_names(),
+                Some(self.player.config_data.theme.clone()),
+                ForegroundEvent::ThemeChanged,
+            )
+            .padding(3)
+            .text_size(12)
+            .width(Length::Fill),
+        ]
+        .spacing(10)
+        .align_y(iced::Alignment::Center);
 
-        let directory_text_input = text_input("Music Directory Path", directory_path_text)
+        let resume_session_toggler = toggler(self.player.config_data.resume_session)
+            .label("Resume Last Session")
+            .on_toggle(ForegroundEvent::ResumeSessionToggled)
+            .spacing(15);
+
+        let start_paused_toggler = toggler(self.player.config_data.start_paused)
+            .label("Start Paused")
+            .on_toggle_maybe(
+                self.player
+                    .config_data
+                    .resume_session
+                    .then_some(ForegroundEvent::StartPausedToggled),
+            )
+            .spacing(15);
+
+        let resume_position_input = widget::row![
+            text("Resume tracks longer than").size(12),
+            text_input("0", &self.resume_position_min_input)
+                .on_input(ForegroundEvent::ResumePositionMinInputChanged)
+                .on_submit(ForegroundEvent::ResumePositionMinSubmitted)
+                .padding(3)
+                .size(12)
+                .width(Length::Fixed(40.0)),
+            text("min").size(12),
+        ]
+        .spacing(5)
+        .align_y(iced::Alignment::Center);
+
+        let directory_path_text = self.music_directory_input.trim();
+        let directory_path = PathBuf::from(directory_path_text);
+
+        let directory_text_input = text_input("Music Directory Path", &self.music_directory_input)
             .id(TEXT_INPUT_ID.clone())
             .on_input(ForegroundEvent::MusicDirectoryInputChanged)
+            .on_submit(ForegroundEvent::MusicDirectorySubmitted)
             .padding(15)
             .size(13);
 
@@ -468,11 +1082,7 @@ impl MainApp {
             ""
         };
 
-        let directory_error_text = text(directory_error_messasge)
-            .style(|_| text::Style {
-                color: Some(Color::from_rgb8(u8::MAX, 0, 0)),
-            })
-            .size(9);
+        let directory_error_text = text(directory_error_messasge).style(text::danger).size(9);
 
         let choose_directory_button = button(text("Choose Directory").size(12))
             .on_press(ForegroundEvent::ChooseMusicDirectory);
@@ -480,7 +1090,14 @@ impl MainApp {
         let content = container(
             column![
                 text("Setting").size(24),
-                column![toggler,].spacing(10),
+                column![
+                    theme_pick_list,
+                    random_toggler,
+                    resume_session_toggler,
+                    start_paused_toggler,
+                    resume_position_input,
+                ]
+                .spacing(10),
                 column![
                     directory_text_input,
                     directory_error_text,
@@ -497,3 +1114,79 @@ impl MainApp {
         content.into()
     }
 }
+
+impl MainApp {
+    fn shortcut_modal_view(&self) -> Element<'_, ForegroundEvent> {
+        let mut shortcut_list = Column::new().spacing(5);
+
+        for action in ShortcutAction::ALL {
+            shortcut_list = shortcut_list.push(
+                widget::row![
+                    text(action.description()).size(12).width(Length::Fill),
+                    text(self.player.config_data.key_bindings.binding(action)).size(12),
+                ]
+                .spacing(10),
+            );
+        }
+
+        let content = container(
+            column![
+                text("Shortcuts").size(24),
+                shortcut_list,
+                text("Esc closes this window").size(10),
+            ]
+            .spacing(20),
+        )
+        .width(250)
+        .padding(10)
+        .style(container::rounded_box);
+
+        content.into()
+    }
+}
+
+/// 앨범 커버 썸네일을 만드는 작업은 화면이 멈추지 않도록 별도 스레드에서 한다.
+fn load_album_art(file_path: PathBuf) -> Task<ForegroundEvent> {
+    let (sender, receiver) = iced::futures::channel::oneshot::channel();
+    let music_path = file_path.clone();
+
+    std::thread::spawn(move || {
+        let thumbnail =
+            album_art::load_thumbnail(&music_path, &album_art::get_album_art_cache_path())
+                .unwrap_or_else(|err| {
+                    log::error!("Failed to load album art: {:?}", err);
+                    None
+                });
+
+        let _ = sender.send(thumbnail);
+    });
+
+    Task::perform(receiver, move |thumbnail| ForegroundEvent::AlbumArtLoaded {
+        file_path: file_path.clone(),
+        thumbnail: thumbnail.ok().flatten(),
+    })
+}
+
+/// iced 키 입력을 단축키 비교용 형태로 바꾼다.
+/// 한글 등 라틴 문자가 아닌 자판에서도 같은 물리 키로 동작하도록 라틴 문자로 변환한다.
+fn key_press(
+    key: iced::keyboard::Key,
+    physical_key: iced::keyboard::key::Physical,
+    modifiers: iced::keyboard::Modifiers,
+) -> Option<KeyPress> {
+    use iced::keyboard::Key;
+
+    let key = match &key {
+        Key::Named(named) => format!("{named:?}").to_lowercase(),
+        Key::Character(_) => key.to_latin(physical_key)?.to_lowercase().to_string(),
+        Key::Unidentified => return None,
+    };
+
+    Some(KeyPress {
+        key,
+        ctrl: modifiers.control(),
+        shift: modifiers.shift(),
+        alt: modifiers.alt(),
+        logo: modifiers.logo(),
+    })
+}
@@ -0,0 +1,36 @@
+use std::hash::{Hash, Hasher};
+
+use iced::Subscription;
+use iced::futures::{Stream, stream};
+
+use crate::ipc::{IpcMessage, IpcReceiver};
+
+/// 소켓은 앱에 하나뿐이므로 항상 같은 구독으로 식별되도록 한다.
+struct IpcSource(IpcReceiver);
+
+impl Hash for IpcSource {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        "ipc-requests".hash(state);
+    }
+}
+
+/// 다른 실행에서 소켓으로 보낸 요청을 iced 구독으로 노출한다.
+pub fn subscription(ipc_receiver: &IpcReceiver) -> Subscription<IpcMessage> {
+    Subscription::run_with(IpcSource(ipc_receiver.clone()), ipc_stream)
+}
+
+fn ipc_stream(source: &IpcSource) -> impl Stream<Item = IpcMessage> + use<> {
+    // 수신자는 하나뿐이므로 처음 구독할 때 꺼내 간다.
+    let receiver = source
+        .0
+        .lock()
+        .ok()
+        .and_then(|mut receiver| receiver.take());
+
+    stream::unfold(receiver, |receiver| async move {
+        let mut receiver = receiver?;
+        let message = receiver.recv().await?;
+
+        Some((message, Some(receiver)))
+    })
+}
@@ -0,0 +1,27 @@
+use std::hash::{Hash, Hasher};
+
+use iced::Subscription;
+use iced::futures::{Stream, stream};
+use musica_core::backend::state::{BackgroundState, BackgroundStatusEvent};
+
+/// 백그라운드 루프는 앱에 하나뿐이므로 항상 같은 구독으로 식별되도록 한다.
+struct StatusSource(BackgroundState);
+
+impl Hash for StatusSource {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        "background-status".hash(state);
+    }
+}
+
+/// 백그라운드 루프의 상태 이벤트를 iced 구독으로 노출한다.
+pub fn subscription(background_state: &BackgroundState) -> Subscription<BackgroundStatusEvent> {
+    Subscription::run_with(StatusSource(background_state.clone()), status_stream)
+}
+
+fn status_stream(source: &StatusSource) -> impl Stream<Item = BackgroundStatusEvent> + use<> {
+    stream::unfold(source.0.status_stream(), |mut status_stream| async move {
+        let event = status_stream.recv().await?;
+
+        Some((event, status_stream))
+    })
+}
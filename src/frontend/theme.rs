@@ -0,0 +1,171 @@
+use iced::theme::{Mode, Palette};
+use iced::widget::{button, container, text};
+use iced::{Background, Border, Theme};
+use musica_core::config::CustomColors;
+
+/// 시스템의 라이트/다크 설정을 따르는 테마 이름
+pub const SYSTEM_THEME: &str = "System";
+
+/// 설정에서 고를 수 있는 테마 이름 목록
+pub fn theme_names() -> Vec<String> {
+    std::iter::once(SYSTEM_THEME.to_string())
+        .chain(Theme::ALL.iter().map(Theme::to_string))
+        .collect()
+}
+
+/// 설정의 테마 이름과 사용자 색상으로 실제 테마를 만든다.
+/// 알 수 없는 이름이면 기본 테마(Dracula)를 사용한다.
+pub fn resolve_theme(name: &str, system_mode: Mode, custom_colors: &CustomColors) -> Theme {
+    let base = if name == SYSTEM_THEME {
+        match system_mode {
+            Mode::Light => Theme::Light,
+            Mode::Dark | Mode::None => Theme::Dark,
+        }
+    } else {
+        Theme::ALL
+            .iter()
+            .find(|theme| theme.to_string() == name)
+            .cloned()
+            .unwrap_or(Theme::Dracula)
+    };
+
+    let accent = parse_color(custom_colors.accent.as_deref());
+    let background = parse_color(custom_colors.background.as_deref());
+
+    if accent.is_none() && background.is_none() {
+        return base;
+    }
+
+    let palette = base.palette();
+
+    Theme::custom(
+        format!("{base} (custom)"),
+        Palette {
+            primary: accent.unwrap_or(palette.primary),
+            background: background.unwrap_or(palette.background),
+            ..palette
+        },
+    )
+}
+
+/// 재생 정보 패널
+pub fn panel(theme: &Theme) -> container::Style {
+    let pair = theme.extended_palette().background.weak;
+
+    container::Style {
+        text_color: Some(pair.text),
+        border: Border {
+            width: 1.0,
+            radius: 10.0.into(),
+            ..Default::default()
+        },
+        background: Some(Background::Color(pair.color)),
+        ..Default::default()
+    }
+}
+
+/// 패널 위의 작은 둥근 버튼 (setting, mini 등)
+pub fn pill_button(theme: &Theme, _status: button::Status) -> button::Style {
+    let pair = theme.extended_palette().secondary.base;
+
+    button::Style {
+        background: Some(Background::Color(pair.color.scale_alpha(0.5))),
+        text_color: pair.text,
+        border: Border {
+            radius: 10.0.into(),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+/// 재생 목록의 한 줄 (재생 중인 곡은 강조 색으로 표시)
+pub fn music_row(theme: &Theme, is_current_music: bool) -> button::Style {
+    let palette = theme.extended_palette();
+
+    let background = if is_current_music {
+        palette.primary.base.color.scale_alpha(0.4)
+    } else {
+        palette.background.strong.color.scale_alpha(0.5)
+    };
+
+    button::Style {
+        background: Some(Background::Color(background)),
+        text_color: palette.background.base.text,
+        border: Border {
+            radius: 0.0.into(),
+            width: 0.1,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+/// 재생할 수 없는 곡이나 목록 머리글처럼 흐리게 표시하는 글자
+pub fn dimmed_text(theme: &Theme) -> text::Style {
+    text::Style {
+        color: Some(theme.palette().text.scale_alpha(0.5)),
+    }
+}
+
+/// 재생 목록의 곡 정보 (재생할 수 없는 곡은 흐리게)
+pub fn music_text(theme: &Theme, is_broken: bool) -> text::Style {
+    if is_broken {
+        dimmed_text(theme)
+    } else {
+        text::Style::default()
+    }
+}
+
+/// 강조 색 글자 (이어서 재생할 위치 등)
+pub fn accent_text(theme: &Theme) -> text::Style {
+    text::Style {
+        color: Some(theme.palette().primary),
+    }
+}
+
+/// "#rrggbb" 형식의 색상을 읽는다. (잘못된 값은 무시)
+fn parse_color(color: Option<&str>) -> Option<iced::Color> {
+    let color = color?.trim();
+
+    match color.parse() {
+        Ok(color) => Some(color),
+        Err(error) => {
+            log::warn!("Invalid color {color:?}: {error}");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_builtin_and_system_themes() {
+        let no_colors = CustomColors::default();
+
+        assert_eq!(Theme::Nord, resolve_theme("Nord", Mode::Dark, &no_colors));
+        assert_eq!(
+            Theme::Light,
+            resolve_theme(SYSTEM_THEME, Mode::Light, &no_colors)
+        );
+        assert_eq!(
+            Theme::Dracula,
+            resolve_theme("unknown", Mode::Dark, &no_colors)
+        );
+    }
+
+    #[test]
+    fn applies_custom_colors_over_base_palette() {
+        let custom_colors = CustomColors {
+            accent: Some("#ff0000".into()),
+            background: Some("not a color".into()),
+        };
+
+        let palette = resolve_theme("Nord", Mode::Dark, &custom_colors).palette();
+
+        assert_eq!(iced::Color::from_rgb8(0xff, 0, 0), palette.primary);
+        assert_eq!(Theme::Nord.palette().background, palette.background);
+    }
+}
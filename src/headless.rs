@@ -0,0 +1,51 @@
+use std::sync::mpsc;
+
+use crate::cli::Cli;
+use crate::control::{self, PlayerEvent};
+use crate::ipc::{IpcReceiver, IpcSender};
+
+/// 창 없이 재생기만 실행한다. (음악 서버 등 화면이 없는 곳에서 사용)
+/// ctl 명령, MPRIS, HTTP API, MPD로 제어하고, quit 요청이나 종료 시그널(SIGTERM, SIGINT)을
+/// 받으면 세션을 저장하고 끝난다.
+pub fn run(cli: &Cli, ipc_sender: IpcSender, ipc_receiver: IpcReceiver) {
+    let mut player = control::start_player(cli, ipc_sender);
+
+    let (event_sender, event_receiver) = mpsc::channel::<PlayerEvent>();
+    forward_signals(event_sender.clone());
+    control::forward_events(&player, &ipc_receiver, event_sender);
+
+    println!("musica is running headless. Use `musica ctl quit` to stop it.");
+
+    for event in event_receiver {
+        if control::handle_event(&mut player, event) {
+            break;
+        }
+    }
+
+    player.shutdown();
+}
+
+/// 종료 시그널을 받으면 quit 요청처럼 루프를 끝내도록 전달한다.
+/// (그냥 종료되면 세션이 저장되지 않고 소켓 파일이 남는다.)
+#[cfg(unix)]
+fn forward_signals(sender: mpsc::Sender<PlayerEvent>) {
+    use signal_hook::consts::{SIGINT, SIGTERM};
+    use signal_hook::iterator::Signals;
+
+    let mut signals = match Signals::new([SIGINT, SIGTERM]) {
+        Ok(signals) => signals,
+        Err(error) => {
+            log::error!("Failed to register signal handler: {:?}", error);
+            return;
+        }
+    };
+
+    std::thread::spawn(move || {
+        if signals.forever().next().is_some() {
+            let _ = sender.send(PlayerEvent::Quit);
+        }
+    });
+}
+
+#[cfg(not(unix))]
+fn forward_signals(_sender: mpsc::Sender<PlayerEvent>) {}
@@ -0,0 +1,552 @@
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use musica_core::backend::state::BackgroundState;
+use musica_core::config;
+use musica_core::state::MusicList;
+use tokio::sync::mpsc as tokio_mpsc;
+
+/// 실행 중인 인스턴스의 응답을 기다리는 최대 시간
+const REPLY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 다른 프로세스(두 번째 실행 등)가 실행 중인 인스턴스에 보내는 요청
+/// 소켓에는 한 줄짜리 JSON으로 주고받는다.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum IpcRequest {
+    /// 파일/폴더/재생 목록을 재생 목록에 추가하고, enqueue가 아니면 첫 곡을 바로 재생
+    Open {
+        paths: Vec<PathBuf>,
+        enqueue: bool,
+    },
+    Play,
+    Pause,
+    Toggle, // 재생 중이면 일시정지, 아니면 재생
+    Stop,
+    Next,
+    Previous,
+    Volume {
+        volume: f32, // 0.0 - 1.0
+    },
+    Shuffle {
+        shuffle: bool,
+    },
+    Seek {
+        offset_millis: i64, // 현재 위치에서 앞(양수)/뒤(음수)로 이동
+    },
+    Status,
+    Queue,   // 재생 목록
+    Library, // 설정한 음악 폴더의 음악 파일
+    PlayIndex {
+        index: usize,
+    },
+    Remove {
+        index: usize,
+    },
+    Clear,
+    Quit, // 실행 중인 인스턴스 종료
+}
+
+/// 재생 목록의 곡 하나 (queue 요청의 응답)
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TrackInfo {
+    pub index: usize,
+    pub title: String, // 태그의 제목 (없으면 파일 이름)
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub track_number: Option<u32>,
+    pub duration_millis: Option<u64>,
+    pub file_path: PathBuf,
+    pub error: Option<String>, // 재생할 수 없는 곡이면 그 이유
+}
+
+impl TrackInfo {
+    pub fn list(music_list: &MusicList) -> Vec<Self> {
+        music_list
+            .list
+            .iter()
+            .enumerate()
+            .map(|(index, music)| Self {
+                index,
+                title: music
+                    .metadata
+                    .title
+                    .clone()
+                    .unwrap_or_else(|| music.title.clone()),
+                artist: music.metadata.artist.clone(),
+                album: music.metadata.album.clone(),
+                track_number: music.metadata.track_number,
+                duration_millis: music
+                    .metadata
+                    .duration
+                    .map(|duration| duration.as_millis() as u64),
+                file_path: music.file_path.clone(),
+                error: music.error.clone(),
+            })
+            .collect()
+    }
+}
+
+/// 재생 상태 (status 요청의 응답)
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PlayerStatus {
+    pub state: PlaybackState,
+    pub index: Option<usize>, // 재생 목록에서의 위치 (목록이 비어 있으면 None)
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub file_path: Option<PathBuf>,
+    pub position_millis: u64,
+    pub duration_millis: Option<u64>,
+    pub volume: f32,
+    pub is_muted: bool,
+    pub shuffle: bool,
+    pub stop_after_current: bool,
+    pub queue_length: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlaybackState {
+    Playing,
+    Paused,
+    Stopped,
+}
+
+impl PlayerStatus {
+    /// 백그라운드 루프의 공유 상태에서 현재 재생 상태를 읽는다.
+    /// 볼륨은 백그라운드 루프가 들고 있지 않으므로 설정 값을 받는다.
+    pub fn read(
+        background_state: &BackgroundState,
+        music_list: &MusicList,
+        volume: f32,
+        is_muted: bool,
+    ) -> Self {
+        let music_index = background_state.current_music_index.load(Ordering::Acquire);
+        let music = music_list.list.get(music_index);
+
+        let state = if background_state.is_stopped.load(Ordering::Acquire) || music.is_none() {
+            PlaybackState::Stopped
+        } else if background_state.is_paused.load(Ordering::Acquire) {
+            PlaybackState::Paused
+        } else {
+            PlaybackState::Playing
+        };
+
+        let duration_millis = background_state.duration_millis.load(Ordering::Acquire);
+
+        Self {
+            state,
+            index: music.map(|_| music_index),
+            title: music.map(|music| {
+                music
+                    .metadata
+                    .title
+                    .clone()
+                    .unwrap_or_else(|| music.title.clone())
+            }),
+            artist: music.and_then(|music| music.metadata.artist.clone()),
+            album: music.and_then(|music| music.metadata.album.clone()),
+            file_path: music.map(|music| music.file_path.clone()),
+            position_millis: background_state.position_millis.load(Ordering::Acquire),
+            duration_millis: (duration_millis > 0).then_some(duration_millis),
+            volume,
+            is_muted,
+            shuffle: background_state.is_random_mode.load(Ordering::Acquire),
+            stop_after_current: background_state.stop_after_current.load(Ordering::Acquire),
+            queue_length: music_list.list.len(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct IpcResponse {
+    pub ok: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<PlayerStatus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub queue: Option<Vec<TrackInfo>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub library: Option<Vec<PathBuf>>,
+}
+
+impl IpcResponse {
+    pub fn ok() -> Self {
+        Self {
+            ok: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn status(status: PlayerStatus) -> Self {
+        Self {
+            ok: true,
+            status: Some(status),
+            ..Default::default()
+        }
+    }
+
+    pub fn queue(queue: Vec<TrackInfo>) -> Self {
+        Self {
+            ok: true,
+            queue: Some(queue),
+            ..Default::default()
+        }
+    }
+
+    pub fn library(library: Vec<PathBuf>) -> Self {
+        Self {
+            ok: true,
+            library: Some(library),
+            ..Default::default()
+        }
+    }
+
+    pub fn error(error: impl Into<String>) -> Self {
+        Self {
+            ok: false,
+            error: Some(error.into()),
+            ..Default::default()
+        }
+    }
+}
+
+/// 받은 요청과 그 응답을 돌려보낼 채널
+#[derive(Debug, Clone)]
+pub struct IpcMessage {
+    pub request: IpcRequest,
+    reply_sender: tokio_mpsc::UnboundedSender<IpcResponse>,
+}
+
+impl IpcMessage {
+    pub fn reply(&self, response: IpcResponse) {
+        // 요청한 쪽이 이미 끊었으면 무시
+        let _ = self.reply_sender.send(response);
+    }
+}
+
+/// 요청을 보내는 쪽 (소켓 연결, MPRIS 등 앱 밖에서 들어온 제어 요청)
+#[derive(Debug, Clone)]
+pub struct IpcSender(tokio_mpsc::UnboundedSender<IpcMessage>);
+
+impl IpcSender {
+    /// 요청을 보내고 처리 결과를 기다린다.
+    pub async fn request(&self, request: IpcRequest) -> IpcResponse {
+        match self.send(request) {
+            // 응답하지 않고 메시지를 버렸으면 None
+            Some(mut reply_receiver) => reply_receiver
+                .recv()
+                .await
+                .unwrap_or_else(|| IpcResponse::error("No response from musica")),
+            None => IpcResponse::error("musica is shutting down"),
+        }
+    }
+
+    /// async 런타임 밖(소켓 처리 스레드 등)에서 쓰는 request
+    pub fn blocking_request(&self, request: IpcRequest) -> IpcResponse {
+        match self.send(request) {
+            Some(mut reply_receiver) => reply_receiver
+                .blocking_recv()
+                .unwrap_or_else(|| IpcResponse::error("No response from musica")),
+            None => IpcResponse::error("musica is shutting down"),
+        }
+    }
+
+    fn send(&self, request: IpcRequest) -> Option<tokio_mpsc::UnboundedReceiver<IpcResponse>> {
+        let (reply_sender, reply_receiver) = tokio_mpsc::unbounded_channel();
+
+        self.0
+            .send(IpcMessage {
+                request,
+                reply_sender,
+            })
+            .ok()?;
+
+        Some(reply_receiver)
+    }
+}
+
+/// 받은 요청을 꺼내는 쪽
+/// iced 구독이 다시 만들어져도 하나의 수신자를 이어서 쓸 수 있도록 공유한다.
+pub type IpcReceiver = Arc<Mutex<Option<tokio_mpsc::UnboundedReceiver<IpcMessage>>>>;
+
+pub fn channel() -> (IpcSender, IpcReceiver) {
+    let (sender, receiver) = tokio_mpsc::unbounded_channel();
+
+    (IpcSender(sender), Arc::new(Mutex::new(Some(receiver))))
+}
+
+pub fn get_socket_path() -> PathBuf {
+    let app_data_path = config::get_app_data_path();
+
+    app_data_path.join("musica.sock")
+}
+
+/// 실행 중인 인스턴스에 요청을 보내고 응답을 기다린다.
+/// 실행 중인 인스턴스가 없으면 오류를 반환한다.
+#[cfg(unix)]
+pub fn send_request(socket_path: &Path, request: &IpcRequest) -> anyhow::Result<IpcResponse> {
+    use std::os::unix::net::UnixStream;
+
+    let mut stream = UnixStream::connect(socket_path)?;
+    stream.set_read_timeout(Some(REPLY_TIMEOUT))?;
+
+    let mut request_str = serde_json::to_string(request)?;
+    request_str.push('\n');
+    stream.write_all(request_str.as_bytes())?;
+
+    let mut response_str = String::new();
+    BufReader::new(stream).read_line(&mut response_str)?;
+
+    Ok(serde_json::from_str(&response_str)?)
+}
+
+#[cfg(not(unix))]
+pub fn send_request(_socket_path: &Path, _request: &IpcRequest) -> anyhow::Result<IpcResponse> {
+    anyhow::bail!("Local socket is not supported on this platform")
+}
+
+/// send_request의 오류가 실행 중인 인스턴스가 없다는 뜻인지 확인한다.
+/// (소켓 파일이 없거나, 남아 있는 소켓 파일에 연결을 받는 쪽이 없을 때)
+/// 응답 시간 초과 등 다른 오류는 인스턴스가 살아 있을 수 있으므로 false
+pub fn is_not_running(error: &anyhow::Error) -> bool {
+    error.downcast_ref::<std::io::Error>().is_some_and(|error| {
+        matches!(
+            error.kind(),
+            std::io::ErrorKind::NotFound | std::io::ErrorKind::ConnectionRefused
+        )
+    })
+}
+
+/// 소켓을 열고 받은 요청을 sender로 넘기기 시작한다.
+/// 소켓 옆의 잠금 파일을 프로세스가 끝날 때까지 잡고 있어서, 다른 인스턴스가 동시에
+/// 확인하고 bind하더라도 살아 있는 소켓을 지우지 않는다.
+/// 잠금을 얻은 뒤 남아 있는 소켓 파일은 이전 실행이 비정상 종료하며 남긴 것이므로 지운다.
+#[cfg(unix)]
+pub fn listen(socket_path: &Path, sender: IpcSender) -> anyhow::Result<()> {
+    use std::fs::{File, TryLockError};
+    use std::os::unix::net::UnixListener;
+
+    if let Some(parent) = socket_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let lock_file = File::create(socket_path.with_extension("lock"))?;
+
+    match lock_file.try_lock() {
+        Ok(()) => {}
+        Err(TryLockError::WouldBlock) => {
+            anyhow::bail!("Another instance is already listening")
+        }
+        Err(TryLockError::Error(error)) => return Err(error.into()),
+    }
+
+    if socket_path.exists() {
+        std::fs::remove_file(socket_path)?;
+    }
+
+    let listener = UnixListener::bind(socket_path)?;
+
+    std::thread::spawn(move || {
+        // 잠금은 이 프로세스가 소켓을 여는 동안 계속 유지한다.
+        let _lock_file = lock_file;
+
+        for stream in listener.incoming().flatten() {
+            let sender = sender.clone();
+
+            std::thread::spawn(move || {
+                if let Err(error) = handle_connection(stream, sender) {
+                    log::error!("Failed to handle IPC request: {:?}", error);
+                }
+            });
+        }
+    });
+
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn listen(_socket_path: &Path, _sender: IpcSender) -> anyhow::Result<()> {
+    anyhow::bail!("Local socket is not supported on this platform")
+}
+
+#[cfg(unix)]
+fn handle_connection(
+    mut stream: std::os::unix::net::UnixStream,
+    sender: IpcSender,
+) -> anyhow::Result<()> {
+    stream.set_read_timeout(Some(REPLY_TIMEOUT))?;
+
+    let mut request_str = String::new();
+    BufReader::new(&stream).read_line(&mut request_str)?;
+
+    let response = match serde_json::from_str(&request_str) {
+        Ok(request) => sender.blocking_request(request),
+        Err(error) => IpcResponse::error(format!("Invalid request: {error}")),
+    };
+
+    let mut response_str = serde_json::to_string(&response)?;
+    response_str.push('\n');
+    stream.write_all(response_str.as_bytes())?;
+
+    Ok(())
+}
+
+/// 받은 요청에 응답하는 프런트엔드 대역을 시작한다. (원격 제어 서버 테스트용)
+/// 상태와 재생 목록 요청에는 주어진 값으로 답하고, 나머지 요청은 기록한 뒤 ok로 답한다.
+#[cfg(test)]
+pub fn spawn_fake_frontend(
+    status: PlayerStatus,
+    queue: Vec<TrackInfo>,
+) -> (IpcSender, Arc<Mutex<Vec<IpcRequest>>>) {
+    let (ipc_sender, ipc_receiver) = channel();
+    let requests = Arc::new(Mutex::new(Vec::new()));
+
+    let recorded = requests.clone();
+    std::thread::spawn(move || {
+        let mut receiver = ipc_receiver.lock().unwrap().take().unwrap();
+
+        while let Some(message) = receiver.blocking_recv() {
+            let response = match message.request {
+                IpcRequest::Status => IpcResponse::status(status.clone()),
+                IpcRequest::Queue => IpcResponse::queue(queue.clone()),
+                ref request => {
+                    recorded.lock().unwrap().push(request.clone());
+                    IpcResponse::ok()
+                }
+            };
+
+            message.reply(response);
+        }
+    });
+
+    (ipc_sender, requests)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use musica_core::state::Music;
+
+    #[test]
+    fn forwards_request_and_reply() {
+        let directory = std::env::temp_dir().join(format!("musica-ipc-{}", std::process::id()));
+        let socket_path = directory.join("musica.sock");
+
+        // 실행 중인 인스턴스가 없으면 전달할 수 없다.
+        let request = IpcRequest::Open {
+            paths: vec![PathBuf::from("/music/a.mp3")],
+            enqueue: true,
+        };
+        assert!(send_request(&socket_path, &request).is_err());
+
+        let (sender, receiver) = channel();
+        listen(&socket_path, sender).unwrap();
+
+        let server = std::thread::spawn(move || {
+            let mut receiver = receiver.lock().unwrap().take().unwrap();
+            let message = receiver.blocking_recv().unwrap();
+            message.reply(IpcResponse::ok());
+            message.request
+        });
+
+        assert_eq!(
+            IpcResponse::ok(),
+            send_request(&socket_path, &request).unwrap()
+        );
+        assert_eq!(request, server.join().unwrap());
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn keeps_socket_of_running_instance() {
+        let directory =
+            std::env::temp_dir().join(format!("musica-ipc-lock-{}", std::process::id()));
+        let socket_path = directory.join("musica.sock");
+
+        let error = send_request(&socket_path, &IpcRequest::Status).unwrap_err();
+        assert!(is_not_running(&error));
+
+        let (sender, receiver) = channel();
+        listen(&socket_path, sender).unwrap();
+
+        // 다른 인스턴스가 열려고 해도 살아 있는 소켓은 지우지 않는다.
+        let (other_sender, _other_receiver) = channel();
+        assert!(listen(&socket_path, other_sender).is_err());
+
+        let server = std::thread::spawn(move || {
+            let mut receiver = receiver.lock().unwrap().take().unwrap();
+            receiver.blocking_recv().unwrap().reply(IpcResponse::ok());
+        });
+
+        assert_eq!(
+            IpcResponse::ok(),
+            send_request(&socket_path, &IpcRequest::Status).unwrap()
+        );
+        server.join().unwrap();
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn stale_socket_means_not_running() {
+        let directory =
+            std::env::temp_dir().join(format!("musica-ipc-stale-{}", std::process::id()));
+        let socket_path = directory.join("musica.sock");
+        std::fs::create_dir_all(&directory).unwrap();
+
+        // 연결을 받는 쪽 없이 소켓 파일만 남은 상태
+        drop(std::os::unix::net::UnixListener::bind(&socket_path).unwrap());
+
+        let error = send_request(&socket_path, &IpcRequest::Status).unwrap_err();
+        assert!(is_not_running(&error));
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn reads_status_from_background_state() {
+        let background_state = BackgroundState::new(true);
+        let music_list = MusicList {
+            list: vec![
+                Music {
+                    title: "a.mp3".into(),
+                    file_path: PathBuf::from("/music/a.mp3"),
+                    ..Default::default()
+                },
+                Music {
+                    title: "b.mp3".into(),
+                    file_path: PathBuf::from("/music/b.mp3"),
+                    ..Default::default()
+                },
+            ],
+        };
+
+        background_state
+            .current_music_index
+            .store(1, Ordering::Release);
+        background_state.is_paused.store(true, Ordering::Release);
+        background_state
+            .position_millis
+            .store(1_500, Ordering::Release);
+
+        let status = PlayerStatus::read(&background_state, &music_list, 0.4, false);
+
+        assert_eq!(PlaybackState::Paused, status.state);
+        assert_eq!(Some(1), status.index);
+        assert_eq!(Some("b.mp3".to_string()), status.title);
+        assert_eq!(1_500, status.position_millis);
+        assert_eq!(None, status.duration_millis);
+        assert!(status.shuffle);
+        assert_eq!(2, status.queue_length);
+
+        let response = serde_json::to_value(IpcResponse::status(status)).unwrap();
+        assert_eq!("paused", response["status"]["state"]);
+    }
+}
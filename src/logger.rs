@@ -0,0 +1,67 @@
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use musica_core::config;
+
+/// 이 앱과 재생 엔진의 진단 메시지만 남긴다. (iced, wgpu 등 의존성의 로그는 제외)
+const LOG_TARGET_PREFIX: &str = "musica";
+
+/// 진단 메시지(log 크레이트)를 내보내는 곳
+/// 보통은 stderr로 보내고, TUI처럼 터미널 화면을 직접 그리는 동안에는 파일에 기록한다.
+struct Logger {
+    sink: Mutex<Box<dyn Write + Send>>,
+}
+
+impl log::Log for Logger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::Level::Info && metadata.target().starts_with(LOG_TARGET_PREFIX)
+    }
+
+    fn log(&self, record: &log::Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let mut sink = self.sink.lock().unwrap();
+        let _ = writeln!(sink, "[{}] {}", record.level(), record.args());
+        let _ = sink.flush();
+    }
+
+    fn flush(&self) {
+        let _ = self.sink.lock().unwrap().flush();
+    }
+}
+
+pub fn get_log_path() -> PathBuf {
+    let app_data_path = config::get_app_data_path();
+
+    app_data_path.join("musica.log")
+}
+
+/// 진단 메시지를 stderr로 보낸다.
+pub fn init_stderr() {
+    init(Box::new(std::io::stderr()));
+}
+
+/// 진단 메시지를 파일로 보낸다. 이전 실행의 기록은 지운다.
+pub fn init_file(log_path: &Path) -> anyhow::Result<()> {
+    if let Some(parent) = log_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    init(Box::new(File::create(log_path)?));
+
+    Ok(())
+}
+
+fn init(sink: Box<dyn Write + Send>) {
+    let logger = Logger {
+        sink: Mutex::new(sink),
+    };
+
+    if log::set_boxed_logger(Box::new(logger)).is_ok() {
+        log::set_max_level(log::LevelFilter::Info);
+    }
+}
@@ -1,36 +1,157 @@
-mod backend;
-mod config;
-mod file;
+mod album_art;
+mod api;
+mod cli;
+mod control;
 mod frontend;
-mod state;
+mod headless;
+mod ipc;
+mod logger;
+mod mpd;
+#[cfg(target_os = "linux")]
+mod mpris;
+mod search;
+mod tui;
 
+use clap::Parser;
+use cli::{Cli, Command};
 use frontend::MainApp;
-use iced::{Settings, Size};
+use iced::{Point, Settings, Size};
+use ipc::IpcRequest;
+use musica_core::config::{self, WindowGeometry};
 
 fn main() -> iced::Result {
-    let config_path = config::get_config_path();
-    config::create_config_if_not_exists(config_path).unwrap();
+    let cli = Cli::parse();
+
+    if let Some(Command::Ctl { command }) = &cli.command {
+        std::process::exit(cli::run_ctl(command));
+    }
+
+    // 이미 실행 중이면 인자를 넘기고 종료 (창과 오디오 출력이 두 개 생기지 않도록)
+    let socket_path = ipc::get_socket_path();
+    let request = IpcRequest::Open {
+        paths: cli.absolute_paths(),
+        enqueue: cli.enqueue,
+    };
+
+    match ipc::send_request(&socket_path, &request) {
+        Ok(response) => {
+            if let Some(error) = response.error {
+                eprintln!("musica: {error}");
+            }
+
+            return Ok(());
+        }
+        Err(error) if ipc::is_not_running(&error) => {}
+        // 응답이 늦을 뿐 실행 중일 수 있으므로 소켓을 건드리지 않고 종료
+        Err(error) => {
+            eprintln!("musica: Failed to reach the running instance: {error}");
+            std::process::exit(1);
+        }
+    }
+
+    // TUI는 터미널 화면을 직접 그리므로 진단 메시지가 화면을 깨뜨리지 않도록 파일에 남긴다.
+    // (이미 실행 중인 인스턴스의 로그를 지우지 않도록 인자를 넘기고 난 뒤에 연다.)
+    if cli.tui {
+        let log_path = logger::get_log_path();
+
+        if let Err(error) = logger::init_file(&log_path) {
+            eprintln!("musica: Failed to open {}: {error}", log_path.display());
+        }
+    } else {
+        logger::init_stderr();
+    }
+
+    let (ipc_sender, ipc_receiver) = ipc::channel();
+    let is_listening = match ipc::listen(&socket_path, ipc_sender.clone()) {
+        Ok(()) => true,
+        // 헤드리스에서는 소켓 없이 ctl quit으로 끌 수 없으므로 실행하지 않는다.
+        Err(error) if cli.headless => {
+            eprintln!(
+                "musica: Failed to listen on {}: {error}",
+                socket_path.display()
+            );
+            std::process::exit(1);
+        }
+        Err(error) => {
+            log::error!("Failed to listen on {}: {:?}", socket_path.display(), error);
+            false
+        }
+    };
+
+    let config_path = cli.config.clone().unwrap_or_else(config::get_config_path);
+    if let Err(error) = config::create_config_if_not_exists(config_path.clone()) {
+        eprintln!(
+            "musica: Failed to create {}: {error}",
+            config_path.display()
+        );
+        if is_listening {
+            let _ = std::fs::remove_file(&socket_path);
+        }
+        std::process::exit(1);
+    }
+
+    if cli.headless || cli.tui {
+        if cli.headless {
+            headless::run(&cli, ipc_sender, ipc_receiver);
+        } else if let Err(error) = tui::run(&cli, ipc_sender, ipc_receiver) {
+            eprintln!("musica: {error}");
+        }
+
+        if is_listening {
+            let _ = std::fs::remove_file(&socket_path);
+        }
+
+        return Ok(());
+    }
+
+    let window_geometry = config::read_config_if_exists(config_path)
+        .map(|config| config.window)
+        .unwrap_or_default();
 
     let setting = Settings {
         id: Some("musica".into()),
         ..Default::default()
     };
 
-    #[allow(unused_mut)]
-    let mut window_setting = iced::window::Settings::default();
+    let mut window_setting = iced::window::Settings {
+        min_size: Some(Size::new(
+            WindowGeometry::MIN_WIDTH,
+            WindowGeometry::MIN_HEIGHT,
+        )),
+        ..Default::default()
+    };
+
+    // 지난번 창 위치를 복원
+    if let (Some(x), Some(y)) = (window_geometry.x, window_geometry.y) {
+        window_setting.position = iced::window::Position::Specific(Point::new(x, y));
+    }
 
     #[cfg(target_os = "linux")]
     {
         window_setting.platform_specific.application_id = "musica".into();
     }
 
-    iced::application(MainApp::default, MainApp::update, MainApp::view)
-        .title("musica")
-        .settings(setting)
-        .window(window_setting)
-        .resizable(false)
-        .window_size(Size::new(300.0, 600.0))
-        .theme(MainApp::theme)
-        .subscription(MainApp::subscription)
-        .run()
+    let result = iced::application(
+        move || MainApp::boot(&cli, ipc_sender.clone(), ipc_receiver.clone()),
+        MainApp::update,
+        MainApp::view,
+    )
+    .title("musica")
+    .settings(setting)
+    .window(window_setting)
+    .exit_on_close_request(false)
+    .resizable(true)
+    .window_size(Size::new(
+        window_geometry.width.max(WindowGeometry::MIN_WIDTH),
+        window_geometry.height.max(WindowGeometry::MIN_HEIGHT),
+    ))
+    .theme(MainApp::theme)
+    .subscription(MainApp::subscription)
+    .run();
+
+    if is_listening {
+        let _ = std::fs::remove_file(&socket_path);
+    }
+
+    result
 }
@@ -0,0 +1,932 @@
+use std::collections::BTreeSet;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::sync::mpsc;
+
+use musica_core::backend::state::{BackgroundState, BackgroundStatusEvent};
+use musica_core::config::MpdConfig;
+
+use crate::control::StatusRelay;
+use crate::ipc::{IpcRequest, IpcResponse, IpcSender, PlaybackState, PlayerStatus, TrackInfo};
+
+/// 접속하면 보내는 인사말의 프로토콜 버전
+const PROTOCOL_VERSION: &str = "0.23.5";
+
+/// idle로 기다릴 수 있는 변경 종류
+const SUBSYSTEMS: [&str; 4] = ["player", "mixer", "options", "playlist"];
+
+// MPD의 ACK 오류 코드
+const ACK_ERROR_ARG: u32 = 2;
+const ACK_ERROR_PASSWORD: u32 = 3;
+const ACK_ERROR_PERMISSION: u32 = 4;
+const ACK_ERROR_UNKNOWN: u32 = 5;
+const ACK_ERROR_NO_EXIST: u32 = 50;
+const ACK_ERROR_SYSTEM: u32 = 52;
+
+/// localhost에 MPD 프로토콜 서버를 열고 실제로 열린 주소를 반환한다. (port가 0이면 임의의 포트)
+/// mpc, ncmpcpp 등이 쓰는 명령 중 일부만 지원하며, 요청은 소켓/MPRIS와 같은 경로로 처리한다.
+/// password가 있으면 password 명령으로 같은 값을 보낸 연결만 재생을 제어할 수 있다.
+pub fn serve(
+    mpd_config: &MpdConfig,
+    password: Option<String>,
+    ipc_sender: IpcSender,
+    background_state: BackgroundState,
+) -> anyhow::Result<SocketAddr> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, mpd_config.port))?;
+    let address = listener.local_addr()?;
+    let status_relay = StatusRelay::start(&background_state);
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let session = Session::new(ipc_sender.clone(), password.clone());
+            let status_relay = status_relay.clone();
+
+            std::thread::spawn(move || {
+                if let Err(error) = handle_connection(stream, session, &status_relay) {
+                    log::error!("Failed to handle MPD client: {:?}", error);
+                }
+            });
+        }
+    });
+
+    Ok(address)
+}
+
+/// 연결 스레드가 기다리는 입력 (클라이언트의 명령 또는 재생 상태 변화)
+enum Input {
+    Line(String),
+    Changed(&'static str),
+    Closed,
+}
+
+/// 명령 실패 응답
+#[derive(Debug, PartialEq)]
+struct Ack {
+    code: u32,
+    message: String,
+}
+
+impl Ack {
+    fn new(code: u32, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+fn handle_connection(
+    mut stream: TcpStream,
+    session: Session,
+    status_relay: &StatusRelay,
+) -> anyhow::Result<()> {
+    let result = run_session(&mut stream, session, status_relay);
+
+    // 명령을 읽는 스레드가 복제한 소켓을 가지고 있으므로 직접 닫아야 연결이 끊긴다.
+    let _ = stream.shutdown(Shutdown::Both);
+
+    result
+}
+
+fn run_session(
+    stream: &mut TcpStream,
+    mut session: Session,
+    status_relay: &StatusRelay,
+) -> anyhow::Result<()> {
+    let (input_sender, input_receiver) = mpsc::channel();
+
+    // idle 중에도 noidle을 받을 수 있도록 명령은 따로 읽어서 넘긴다.
+    {
+        let reader = BufReader::new(stream.try_clone()?);
+        let input_sender = input_sender.clone();
+
+        std::thread::spawn(move || {
+            for line in reader.lines() {
+                let Ok(line) = line else { break };
+
+                if input_sender.send(Input::Line(line)).is_err() {
+                    return;
+                }
+            }
+
+            let _ = input_sender.send(Input::Closed);
+        });
+    }
+
+    // 연결이 끊기면 다음 이벤트를 보낼 때 send가 실패해서 목록에서 빠진다.
+    status_relay.subscribe(move |event| {
+        subsystems_of(event)
+            .iter()
+            .all(|&subsystem| input_sender.send(Input::Changed(subsystem)).is_ok())
+    });
+
+    let mut changed = BTreeSet::new(); // 마지막 idle 이후 바뀐 것
+    // 브라우저가 보낸 HTTP 요청의 본문이 MPD 명령으로 실행되지 않도록, 올바른 명령을 받기 전에는
+    // HTTP처럼 보이는 줄이나 모르는 명령을 받으면 연결을 끊는다.
+    let mut is_mpd_client = false;
+    let mut command_list: Option<(bool, Vec<Vec<String>>)> = None; // (list_OK 여부, 명령)
+
+    writeln!(stream, "OK MPD {PROTOCOL_VERSION}")?;
+
+    loop {
+        let line = match input_receiver.recv() {
+            Ok(Input::Line(line)) => line,
+            Ok(Input::Changed(subsystem)) => {
+                changed.insert(subsystem);
+                continue;
+            }
+            Ok(Input::Closed) | Err(_) => return Ok(()),
+        };
+
+        if !is_mpd_client && looks_like_http(&line) {
+            return Ok(());
+        }
+
+        let args = match tokenize(&line) {
+            Ok(args) if !args.is_empty() => args,
+            Ok(_) => continue,
+            Err(_) if !is_mpd_client => return Ok(()),
+            Err(ack) => {
+                write_ack(stream, &ack, 0, "")?;
+                continue;
+            }
+        };
+
+        if !is_mpd_client {
+            if !is_known_command(&args[0]) {
+                return Ok(());
+            }
+
+            is_mpd_client = true;
+        }
+
+        if let Some((list_ok, commands)) = &mut command_list {
+            if args[0] != "command_list_end" {
+                commands.push(args);
+                continue;
+            }
+
+            let list_ok = *list_ok;
+            let commands = std::mem::take(commands);
+            command_list = None;
+
+            let mut output = String::new();
+            let mut failed = None;
+
+            for (index, args) in commands.iter().enumerate() {
+                match session.execute(&args[0], &args[1..]) {
+                    Ok(result) => {
+                        output.push_str(&result);
+
+                        if list_ok {
+                            output.push_str("list_OK\n");
+                        }
+                    }
+                    Err(ack) => {
+                        failed = Some((index, ack));
+                        break;
+                    }
+                }
+            }
+
+            stream.write_all(output.as_bytes())?;
+
+            match failed {
+                Some((index, ack)) => write_ack(stream, &ack, index, &commands[index][0])?,
+                None => stream.write_all(b"OK\n")?,
+            }
+            continue;
+        }
+
+        match args[0].as_str() {
+            "command_list_begin" => command_list = Some((false, Vec::new())),
+            "command_list_ok_begin" => command_list = Some((true, Vec::new())),
+            "close" => return Ok(()),
+            // idle 중이 아닐 때의 noidle은 무시한다.
+            "noidle" => {}
+            "idle" => {
+                let subsystems = if args.len() > 1 {
+                    args[1..].iter().map(String::as_str).collect::<Vec<_>>()
+                } else {
+                    SUBSYSTEMS.to_vec()
+                };
+
+                let is_watched = |subsystem: &str| subsystems.contains(&subsystem);
+
+                // 지난 idle 이후 바뀐 것이 없으면 바뀌거나 noidle을 받을 때까지 기다린다.
+                while !changed.iter().any(|subsystem| is_watched(subsystem)) {
+                    match input_receiver.recv() {
+                        Ok(Input::Changed(subsystem)) => {
+                            changed.insert(subsystem);
+                        }
+                        Ok(Input::Line(line)) if line.trim() == "noidle" => break,
+                        // idle 중에는 noidle 외의 명령을 받을 수 없다.
+                        Ok(Input::Line(_)) | Ok(Input::Closed) | Err(_) => return Ok(()),
+                    }
+                }
+
+                let mut output = String::new();
+
+                changed.retain(|subsystem| {
+                    if is_watched(subsystem) {
+                        output.push_str(&format!("changed: {subsystem}\n"));
+                        false
+                    } else {
+                        true
+                    }
+                });
+                output.push_str("OK\n");
+
+                stream.write_all(output.as_bytes())?;
+            }
+            command => match session.execute(command, &args[1..]) {
+                Ok(output) => {
+                    stream.write_all(output.as_bytes())?;
+                    stream.write_all(b"OK\n")?;
+                }
+                Err(ack) => write_ack(stream, &ack, 0, command)?,
+            },
+        }
+    }
+}
+
+/// HTTP 요청 줄("POST / HTTP/1.1")이나 헤더 줄("Host: ...")처럼 보이는지 확인한다.
+fn looks_like_http(line: &str) -> bool {
+    let line = line.trim();
+    let is_request_line = line
+        .rsplit(' ')
+        .next()
+        .is_some_and(|word| word.starts_with("HTTP/"));
+    let is_header = line.split_once(':').is_some_and(|(name, _)| {
+        !name.is_empty()
+            && name
+                .chars()
+                .all(|char| char.is_ascii_alphanumeric() || char == '-')
+    });
+
+    is_request_line || is_header
+}
+
+/// 이 서버가 처리하는 명령인지 확인한다.
+fn is_known_command(command: &str) -> bool {
+    const CONNECTION_COMMANDS: [&str; 6] = [
+        "command_list_begin",
+        "command_list_ok_begin",
+        "command_list_end",
+        "close",
+        "noidle",
+        "idle",
+    ];
+
+    CONNECTION_COMMANDS.contains(&command) || Session::COMMANDS.contains(&command)
+}
+
+fn write_ack(stream: &mut TcpStream, ack: &Ack, index: usize, command: &str) -> anyhow::Result<()> {
+    writeln!(
+        stream,
+        "ACK [{}@{}] {{{}}} {}",
+        ack.code, index, command, ack.message
+    )?;
+
+    Ok(())
+}
+
+/// idle 클라이언트에 알릴 변경 종류 (재생 위치처럼 알리지 않는 이벤트는 빈 목록)
+fn subsystems_of(event: &BackgroundStatusEvent) -> &'static [&'static str] {
+    match event {
+        BackgroundStatusEvent::MusicChanged(_) => &["player"],
+        BackgroundStatusEvent::PlaylistChanged => &["playlist"],
+        BackgroundStatusEvent::PauseChanged(_) | BackgroundStatusEvent::StopChanged(_) => {
+            &["player"]
+        }
+        BackgroundStatusEvent::VolumeChanged(_) => &["mixer"],
+        BackgroundStatusEvent::RandomModeChanged(_) => &["options"],
+        _ => &[],
+    }
+}
+
+/// 명령 줄을 인자로 나눈다. 큰따옴표로 감싼 인자 안에서는 백슬래시로 이스케이프한다.
+fn tokenize(line: &str) -> Result<Vec<String>, Ack> {
+    let mut args = Vec::new();
+    let mut chars = line.trim().chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        let mut arg = String::new();
+
+        if c == '"' {
+            chars.next();
+
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') => match chars.next() {
+                        Some(c) => arg.push(c),
+                        None => return Err(Ack::new(ACK_ERROR_ARG, "Missing closing '\"'")),
+                    },
+                    Some(c) => arg.push(c),
+                    None => return Err(Ack::new(ACK_ERROR_ARG, "Missing closing '\"'")),
+                }
+            }
+        } else {
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() {
+                    break;
+                }
+
+                arg.push(c);
+                chars.next();
+            }
+        }
+
+        args.push(arg);
+    }
+
+    Ok(args)
+}
+
+/// 한 클라이언트의 명령을 musica 요청으로 바꿔 처리한다.
+struct Session {
+    ipc_sender: IpcSender,
+    password: Option<String>,
+    is_authorized: bool, // password가 없거나 password 명령으로 맞는 값을 보냄
+}
+
+impl Session {
+    /// execute가 처리하는 명령
+    const COMMANDS: [&str; 16] = [
+        "ping",
+        "password",
+        "status",
+        "currentsong",
+        "play",
+        "pause",
+        "stop",
+        "next",
+        "previous",
+        "setvol",
+        "playlistinfo",
+        "add",
+        "clear",
+        "random",
+        "repeat",
+        "seekcur",
+    ];
+
+    fn new(ipc_sender: IpcSender, password: Option<String>) -> Self {
+        Self {
+            ipc_sender,
+            is_authorized: password.is_none(),
+            password,
+        }
+    }
+    fn request(&self, request: IpcRequest) -> Result<IpcResponse, Ack> {
+        let response = self.ipc_sender.blocking_request(request);
+
+        if response.ok {
+            Ok(response)
+        } else {
+            Err(Ack::new(
+                ACK_ERROR_SYSTEM,
+                response.error.unwrap_or_default(),
+            ))
+        }
+    }
+
+    /// 응답 없이 성공만 확인하는 명령
+    fn run(&self, request: IpcRequest) -> Result<String, Ack> {
+        self.request(request).map(|_| String::new())
+    }
+
+    fn status(&self) -> Result<PlayerStatus, Ack> {
+        self.request(IpcRequest::Status)?
+            .status
+            .ok_or_else(|| Ack::new(ACK_ERROR_SYSTEM, "No status"))
+    }
+
+    fn queue(&self) -> Result<Vec<TrackInfo>, Ack> {
+        Ok(self.request(IpcRequest::Queue)?.queue.unwrap_or_default())
+    }
+
+    fn execute(&mut self, command: &str, args: &[String]) -> Result<String, Ack> {
+        match command {
+            "ping" => Ok(String::new()),
+            "password" => {
+                let is_correct = match (&self.password, args.first()) {
+                    (Some(password), Some(given)) => crate::api::is_same_token(password, given),
+                    (password, given) => password.is_none() && given.is_none(),
+                };
+                if !is_correct {
+                    return Err(Ack::new(ACK_ERROR_PASSWORD, "incorrect password"));
+                }
+
+                self.is_authorized = true;
+                Ok(String::new())
+            }
+            _ if !self.is_authorized => Err(Ack::new(
+                ACK_ERROR_PERMISSION,
+                format!("you don't have permission for \"{command}\""),
+            )),
+            "status" => {
+                let status = self.status()?;
+                let queue = self.queue()?;
+
+                Ok(format_status(&status, &queue))
+            }
+            "currentsong" => {
+                let status = self.status()?;
+                let queue = self.queue()?;
+
+                Ok(status
+                    .index
+                    .and_then(|index| queue.get(index))
+                    .map(format_track)
+                    .unwrap_or_default())
+            }
+            "play" => match args.first() {
+                Some(position) => self.run(IpcRequest::PlayIndex {
+                    index: self.song_position(position)?,
+                }),
+                None => self.run(IpcRequest::Play),
+            },
+            "pause" => match args.first().map(String::as_str) {
+                Some("1") => self.run(IpcRequest::Pause),
+                Some("0") => self.run(IpcRequest::Play),
+                Some(arg) => Err(Ack::new(ACK_ERROR_ARG, format!("Boolean expected: {arg}"))),
+                None => self.run(IpcRequest::Toggle),
+            },
+            "stop" => self.run(IpcRequest::Stop),
+            "next" => self.run(IpcRequest::Next),
+            "previous" => self.run(IpcRequest::Previous),
+            "setvol" => {
+                let volume = args
+                    .first()
+                    .and_then(|arg| arg.parse::<u8>().ok())
+                    .filter(|volume| *volume <= 100)
+                    .ok_or_else(|| Ack::new(ACK_ERROR_ARG, "Invalid volume value"))?;
+
+                self.run(IpcRequest::Volume {
+                    volume: volume as f32 / 100.0,
+                })
+            }
+            "playlistinfo" => {
+                let queue = self.queue()?;
+
+                let range = match args.first() {
+                    Some(arg) => parse_range(arg)?,
+                    None => 0..queue.len(),
+                };
+
+                if !args.is_empty() && (range.start >= queue.len() || range.is_empty()) {
+                    return Err(Ack::new(ACK_ERROR_ARG, "Bad song index"));
+                }
+
+                Ok(queue[range.start..range.end.min(queue.len())]
+                    .iter()
+                    .map(format_track)
+                    .collect())
+            }
+            "add" => {
+                let uri = args
+                    .first()
+                    .ok_or_else(|| Ack::new(ACK_ERROR_ARG, "Missing argument"))?;
+
+                self.run(IpcRequest::Open {
+                    paths: vec![uri.into()],
+                    enqueue: true,
+                })
+            }
+            "clear" => self.run(IpcRequest::Clear),
+            "random" => match args.first().map(String::as_str) {
+                Some("1") => self.run(IpcRequest::Shuffle { shuffle: true }),
+                Some("0") => self.run(IpcRequest::Shuffle { shuffle: false }),
+                _ => Err(Ack::new(ACK_ERROR_ARG, "Boolean expected")),
+            },
+            // musica는 항상 목록 전체를 반복한다.
+            "repeat" => match args.first().map(String::as_str) {
+                Some("1") => Ok(String::new()),
+                Some("0") => Err(Ack::new(
+                    ACK_ERROR_ARG,
+                    "Turning repeat off is not supported",
+                )),
+                _ => Err(Ack::new(ACK_ERROR_ARG, "Boolean expected")),
+            },
+            "seekcur" => {
+                let arg = args
+                    .first()
+                    .ok_or_else(|| Ack::new(ACK_ERROR_ARG, "Missing argument"))?;
+                let secs = arg
+                    .parse::<f64>()
+                    .map_err(|_| Ack::new(ACK_ERROR_ARG, format!("Invalid time: {arg}")))?;
+                let millis = (secs * 1000.0) as i64;
+
+                // +/-로 시작하면 현재 위치 기준, 아니면 곡의 처음 기준
+                let offset_millis = if arg.starts_with(['+', '-']) {
+                    millis
+                } else {
+                    millis - self.status()?.position_millis as i64
+                };
+
+                self.run(IpcRequest::Seek { offset_millis })
+            }
+            _ => Err(Ack::new(
+                ACK_ERROR_UNKNOWN,
+                format!("unknown command \"{command}\""),
+            )),
+        }
+    }
+
+    fn song_position(&self, arg: &str) -> Result<usize, Ack> {
+        let position = arg
+            .parse::<usize>()
+            .map_err(|_| Ack::new(ACK_ERROR_ARG, format!("Integer expected: {arg}")))?;
+
+        if position >= self.queue()?.len() {
+            return Err(Ack::new(ACK_ERROR_NO_EXIST, "Bad song index"));
+        }
+
+        Ok(position)
+    }
+}
+
+/// "3" 또는 "START:END" 형식의 곡 범위 (END가 없으면 끝까지)
+fn parse_range(arg: &str) -> Result<std::ops::Range<usize>, Ack> {
+    let parse = |value: &str| {
+        value
+            .parse::<usize>()
+            .map_err(|_| Ack::new(ACK_ERROR_ARG, format!("Integer expected: {value}")))
+    };
+
+    match arg.split_once(':') {
+        Some((start, "")) => Ok(parse(start)?..usize::MAX),
+        Some((start, end)) => Ok(parse(start)?..parse(end)?),
+        None => {
+            let position = parse(arg)?;
+            Ok(position..position + 1)
+        }
+    }
+}
+
+fn format_status(status: &PlayerStatus, queue: &[TrackInfo]) -> String {
+    let volume = if status.is_muted {
+        0
+    } else {
+        (status.volume * 100.0).round() as u32
+    };
+
+    let state = match status.state {
+        PlaybackState::Playing => "play",
+        PlaybackState::Paused => "pause",
+        PlaybackState::Stopped => "stop",
+    };
+
+    let mut output = format!(
+        "volume: {volume}\nrepeat: 1\nrandom: {}\nsingle: 0\nconsume: 0\nplaylist: {}\nplaylistlength: {}\nstate: {state}\n",
+        status.shuffle as u8,
+        playlist_version(queue),
+        queue.len(),
+    );
+
+    if let Some(index) = status.index {
+        output.push_str(&format!("song: {index}\nsongid: {index}\n"));
+    }
+
+    if status.state != PlaybackState::Stopped {
+        let elapsed = status.position_millis as f64 / 1000.0;
+        let duration = status.duration_millis.unwrap_or_default() as f64 / 1000.0;
+
+        output.push_str(&format!(
+            "time: {}:{}\nelapsed: {elapsed:.3}\n",
+            elapsed.round() as u64,
+            duration.round() as u64,
+        ));
+
+        if status.duration_millis.is_some() {
+            output.push_str(&format!("duration: {duration:.3}\n"));
+        }
+    }
+
+    output
+}
+
+fn format_track(track: &TrackInfo) -> String {
+    let mut output = format!(
+        "file: {}\nTitle: {}\n",
+        track.file_path.display(),
+        track.title
+    );
+
+    if let Some(artist) = &track.artist {
+        output.push_str(&format!("Artist: {artist}\n"));
+    }
+
+    if let Some(album) = &track.album {
+        output.push_str(&format!("Album: {album}\n"));
+    }
+
+    if let Some(track_number) = track.track_number {
+        output.push_str(&format!("Track: {track_number}\n"));
+    }
+
+    if let Some(duration_millis) = track.duration_millis {
+        output.push_str(&format!(
+            "Time: {}\nduration: {:.3}\n",
+            (duration_millis as f64 / 1000.0).round() as u64,
+            duration_millis as f64 / 1000.0
+        ));
+    }
+
+    output.push_str(&format!("Pos: {}\nId: {}\n", track.index, track.index));
+
+    output
+}
+
+/// 클라이언트가 재생 목록 변경을 알아차릴 수 있도록 목록의 경로로 버전을 만든다.
+fn playlist_version(queue: &[TrackInfo]) -> u32 {
+    let mut hasher = DefaultHasher::new();
+
+    for track in queue {
+        track.file_path.hash(&mut hasher);
+    }
+
+    (hasher.finish() as u32).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use super::*;
+    use crate::ipc;
+
+    /// 상태/재생 목록 요청에는 고정된 값으로 답하는 프런트엔드 대역과 함께 서버를 연다.
+    fn start(password: Option<&str>) -> (SocketAddr, BackgroundState, Arc<Mutex<Vec<IpcRequest>>>) {
+        let queue = ["a", "b"]
+            .iter()
+            .enumerate()
+            .map(|(index, name)| TrackInfo {
+                index,
+                title: name.to_uppercase(),
+                artist: Some("Artist".into()),
+                album: None,
+                track_number: Some(index as u32 + 1),
+                duration_millis: Some(60_000),
+                file_path: PathBuf::from(format!("/music/{name}.mp3")),
+                error: None,
+            })
+            .collect();
+        let (ipc_sender, requests) = ipc::spawn_fake_frontend(
+            PlayerStatus {
+                state: PlaybackState::Playing,
+                index: Some(1),
+                title: Some("Second".into()),
+                artist: None,
+                album: None,
+                file_path: Some(PathBuf::from("/music/b.mp3")),
+                position_millis: 10_000,
+                duration_millis: Some(60_000),
+                volume: 0.5,
+                is_muted: false,
+                shuffle: true,
+                stop_after_current: false,
+                queue_length: 2,
+            },
+            queue,
+        );
+        let background_state = BackgroundState::new(false);
+
+        let mpd_config = MpdConfig {
+            enabled: true,
+            port: 0,
+        };
+        let address = serve(
+            &mpd_config,
+            password.map(String::from),
+            ipc_sender,
+            background_state.clone(),
+        )
+        .unwrap();
+
+        (address, background_state, requests)
+    }
+
+    struct Client {
+        reader: BufReader<TcpStream>,
+        writer: TcpStream,
+    }
+
+    impl Client {
+        fn connect(address: SocketAddr) -> Self {
+            let stream = TcpStream::connect(address).unwrap();
+            stream
+                .set_read_timeout(Some(Duration::from_secs(5)))
+                .unwrap();
+
+            let mut client = Self {
+                reader: BufReader::new(stream.try_clone().unwrap()),
+                writer: stream,
+            };
+            assert!(client.read_line().starts_with("OK MPD "));
+
+            client
+        }
+
+        fn read_line(&mut self) -> String {
+            let mut line = String::new();
+            self.reader.read_line(&mut line).unwrap();
+            line.trim_end().to_string()
+        }
+
+        fn send(&mut self, command: &str) {
+            writeln!(self.writer, "{command}").unwrap();
+        }
+
+        /// 명령을 보내고 OK나 ACK까지의 응답 줄을 반환한다.
+        fn command(&mut self, command: &str) -> Vec<String> {
+            self.send(command);
+            self.read_response()
+        }
+
+        fn read_response(&mut self) -> Vec<String> {
+            let mut lines = Vec::new();
+
+            loop {
+                let line = self.read_line();
+                let is_end = line == "OK" || line.starts_with("ACK ");
+                lines.push(line);
+
+                if is_end {
+                    return lines;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn answers_status_and_playlist_queries() {
+        let (address, _background_state, _requests) = start(None);
+        let mut client = Client::connect(address);
+
+        let status = client.command("status");
+        for line in [
+            "volume: 50",
+            "random: 1",
+            "playlistlength: 2",
+            "state: play",
+            "song: 1",
+            "elapsed: 10.000",
+            "duration: 60.000",
+        ] {
+            assert!(status.contains(&line.to_string()), "{line} in {status:?}");
+        }
+
+        let current_song = client.command("currentsong");
+        assert!(current_song.contains(&"file: /music/b.mp3".to_string()));
+        assert!(current_song.contains(&"Title: B".to_string()));
+        assert!(current_song.contains(&"Pos: 1".to_string()));
+
+        let playlist = client.command("playlistinfo");
+        assert_eq!(
+            2,
+            playlist
+                .iter()
+                .filter(|line| line.starts_with("file: "))
+                .count()
+        );
+        assert_eq!(
+            vec!["file: /music/a.mp3"],
+            client
+                .command("playlistinfo 0")
+                .iter()
+                .filter(|line| line.starts_with("file: "))
+                .collect::<Vec<_>>()
+        );
+
+        assert_eq!(
+            vec!["ACK [5@0] {foo} unknown command \"foo\""],
+            client.command("foo")
+        );
+        assert_eq!(
+            vec!["ACK [50@0] {play} Bad song index"],
+            client.command("play 5")
+        );
+    }
+
+    #[test]
+    fn maps_commands_to_requests() {
+        let (address, _background_state, requests) = start(None);
+        let mut client = Client::connect(address);
+
+        assert_eq!(vec!["OK"], client.command("setvol 30"));
+        assert_eq!(vec!["OK"], client.command("pause 1"));
+        assert_eq!(vec!["OK"], client.command("seekcur 25"));
+        assert_eq!(vec!["OK"], client.command("seekcur -5"));
+        assert_eq!(vec!["OK"], client.command("add \"Some Album/a b.mp3\""));
+        assert_eq!(vec!["OK"], client.command("play 0"));
+
+        client.send("command_list_ok_begin");
+        client.send("random 0");
+        client.send("clear");
+        client.send("command_list_end");
+        assert_eq!(vec!["list_OK", "list_OK", "OK"], client.read_response());
+
+        assert_eq!(
+            vec![
+                IpcRequest::Volume { volume: 0.3 },
+                IpcRequest::Pause,
+                IpcRequest::Seek {
+                    offset_millis: 15_000
+                },
+                IpcRequest::Seek {
+                    offset_millis: -5_000
+                },
+                IpcRequest::Open {
+                    paths: vec![PathBuf::from("Some Album/a b.mp3")],
+                    enqueue: true,
+                },
+                IpcRequest::PlayIndex { index: 0 },
+                IpcRequest::Shuffle { shuffle: false },
+                IpcRequest::Clear,
+            ],
+            *requests.lock().unwrap()
+        );
+    }
+
+    #[test]
+    fn closes_http_requests_without_running_body() {
+        let (address, _background_state, requests) = start(None);
+        let mut client = Client::connect(address);
+
+        // 브라우저가 다른 사이트에서 보낸 POST 요청
+        client.send("POST / HTTP/1.1\r\nHost: 127.0.0.1:6600\r\nContent-Type: text/plain\r\n\r\nclear\nplay 0");
+
+        let mut rest = String::new();
+        let _ = client.reader.read_to_string(&mut rest);
+        assert_eq!("", rest);
+
+        // 모르는 명령으로 시작한 연결도 끊는다.
+        let mut client = Client::connect(address);
+        client.send("foo");
+        client.send("clear");
+        let _ = client.reader.read_to_string(&mut rest);
+        assert_eq!("", rest);
+
+        assert!(requests.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn requires_password_when_configured() {
+        let (address, _background_state, requests) = start(Some("secret"));
+        let mut client = Client::connect(address);
+
+        assert_eq!(vec!["OK"], client.command("ping"));
+        assert_eq!(
+            vec!["ACK [4@0] {clear} you don't have permission for \"clear\""],
+            client.command("clear")
+        );
+        assert_eq!(
+            vec!["ACK [3@0] {password} incorrect password"],
+            client.command("password wrong")
+        );
+        assert!(requests.lock().unwrap().is_empty());
+
+        assert_eq!(vec!["OK"], client.command("password secret"));
+        assert_eq!(vec!["OK"], client.command("clear"));
+        assert_eq!(vec![IpcRequest::Clear], *requests.lock().unwrap());
+    }
+
+    #[test]
+    fn idle_waits_for_changes_until_noidle() {
+        let (address, background_state, _requests) = start(None);
+        let mut client = Client::connect(address);
+
+        // 구독이 시작된 뒤에 이벤트를 보내도록 명령 하나를 먼저 주고받는다.
+        client.command("ping");
+
+        client.send("idle mixer");
+        background_state.publish(BackgroundStatusEvent::PauseChanged(true));
+        background_state.publish(BackgroundStatusEvent::VolumeChanged(0.2));
+        assert_eq!(vec!["changed: mixer", "OK"], client.read_response());
+
+        // 기다리지 않던 변경은 남아 있다가 다음 idle에서 바로 알린다.
+        assert_eq!(vec!["changed: player", "OK"], client.command("idle"));
+
+        // 재생 목록을 비워도 playlist 변경을 알린다.
+        client.send("idle playlist");
+        background_state.publish(BackgroundStatusEvent::PlaylistChanged);
+        assert_eq!(vec!["changed: playlist", "OK"], client.read_response());
+
+        client.send("idle");
+        client.send("noidle");
+        assert_eq!(vec!["OK"], client.read_response());
+    }
+}
@@ -0,0 +1,610 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use musica_core::backend::state::{BackgroundState, BackgroundStatusEvent};
+use zbus::object_server::SignalEmitter;
+use zbus::zvariant::{ObjectPath, OwnedValue, Value};
+use zbus::{fdo, interface};
+
+use crate::ipc::{IpcRequest, IpcResponse, IpcSender, PlaybackState, PlayerStatus};
+
+/// 데스크톱의 미디어 컨트롤(GNOME, KDE, playerctl 등)이 찾는 이름
+const BUS_NAME: &str = "org.mpris.MediaPlayer2.musica";
+const OBJECT_PATH: &str = "/org/mpris/MediaPlayer2";
+
+/// 곡마다 붙이는 MPRIS 트랙 ID의 앞부분 (뒤에 재생 목록 인덱스를 붙임)
+const TRACK_ID_PREFIX: &str = "/org/musica/track/";
+
+/// 세션 버스에 MPRIS 인터페이스를 연다. (address가 None이면 기본 세션 버스)
+/// 재생 제어는 소켓 요청과 같은 경로로 처리하고, 백그라운드 루프의 상태 이벤트로 변경 신호를 보낸다.
+pub fn serve(address: Option<String>, ipc_sender: IpcSender, background_state: BackgroundState) {
+    std::thread::spawn(move || {
+        futures_lite::future::block_on(async move {
+            if let Err(error) = run(address, ipc_sender, background_state).await {
+                log::error!("Failed to serve MPRIS: {:?}", error);
+            }
+        })
+    });
+}
+
+async fn run(
+    address: Option<String>,
+    ipc_sender: IpcSender,
+    background_state: BackgroundState,
+) -> anyhow::Result<()> {
+    // 연결 전에 구독해야 그 사이의 변화를 놓치지 않는다.
+    let mut status_stream = background_state.status_changes();
+
+    let builder = match address {
+        Some(address) => zbus::connection::Builder::address(address.as_str())?,
+        None => zbus::connection::Builder::session()?,
+    };
+
+    let connection = builder
+        .name(BUS_NAME)?
+        .serve_at(OBJECT_PATH, Root)?
+        .serve_at(OBJECT_PATH, Player { ipc_sender })?
+        .build()
+        .await?;
+
+    let player = connection
+        .object_server()
+        .interface::<_, Player>(OBJECT_PATH)
+        .await?;
+
+    while let Some(event) = status_stream.recv().await {
+        let player_ref = player.get().await;
+        let emitter = player.signal_emitter();
+
+        let result = match event {
+            BackgroundStatusEvent::MusicChanged(_) => {
+                match player_ref.metadata_changed(emitter).await {
+                    Ok(()) => player_ref.playback_status_changed(emitter).await,
+                    Err(error) => Err(error),
+                }
+            }
+            BackgroundStatusEvent::PauseChanged(_) | BackgroundStatusEvent::StopChanged(_) => {
+                player_ref.playback_status_changed(emitter).await
+            }
+            // 태그를 읽기 전에 보낸 제목(파일 이름)을 읽은 태그로 바꾼다.
+            BackgroundStatusEvent::MetadataLoaded => player_ref.metadata_changed(emitter).await,
+            BackgroundStatusEvent::VolumeChanged(_) => player_ref.volume_changed(emitter).await,
+            BackgroundStatusEvent::RandomModeChanged(_) => {
+                player_ref.shuffle_changed(emitter).await
+            }
+            _ => Ok(()),
+        };
+
+        if let Err(error) = result {
+            log::error!("Failed to emit MPRIS signal: {:?}", error);
+        }
+    }
+
+    Ok(())
+}
+
+struct Root;
+
+#[interface(name = "org.mpris.MediaPlayer2")]
+impl Root {
+    fn raise(&self) {}
+
+    fn quit(&self) {}
+
+    #[zbus(property)]
+    fn can_quit(&self) -> bool {
+        false
+    }
+
+    #[zbus(property)]
+    fn can_raise(&self) -> bool {
+        false
+    }
+
+    #[zbus(property)]
+    fn has_track_list(&self) -> bool {
+        false
+    }
+
+    #[zbus(property)]
+    fn identity(&self) -> &str {
+        "musica"
+    }
+
+    #[zbus(property)]
+    fn desktop_entry(&self) -> &str {
+        "musica"
+    }
+
+    #[zbus(property)]
+    fn supported_uri_schemes(&self) -> Vec<String> {
+        vec!["file".into()]
+    }
+
+    #[zbus(property)]
+    fn supported_mime_types(&self) -> Vec<String> {
+        vec!["audio/mpeg".into(), "audio/ogg".into(), "audio/flac".into()]
+    }
+}
+
+struct Player {
+    ipc_sender: IpcSender,
+}
+
+impl Player {
+    async fn request(&self, request: IpcRequest) -> fdo::Result<IpcResponse> {
+        let response = self.ipc_sender.request(request).await;
+
+        match response.error {
+            Some(error) => Err(fdo::Error::Failed(error)),
+            None => Ok(response),
+        }
+    }
+
+    async fn status(&self) -> fdo::Result<PlayerStatus> {
+        self.request(IpcRequest::Status)
+            .await?
+            .status
+            .ok_or_else(|| fdo::Error::Failed("No status".into()))
+    }
+
+    /// 현재 위치에서 offset만큼 이동하고 Seeked 신호를 보낸다. (마이크로초)
+    async fn seek_by(&self, offset: i64, emitter: &SignalEmitter<'_>) -> fdo::Result<()> {
+        let status = self.status().await?;
+        let position = (status.position_millis as i64 * 1000).saturating_add(offset);
+        let position = match status.duration_millis {
+            Some(duration_millis) => position.clamp(0, duration_millis as i64 * 1000),
+            None => position.max(0),
+        };
+
+        self.request(IpcRequest::Seek {
+            offset_millis: offset / 1000,
+        })
+        .await?;
+
+        Self::seeked(emitter, position).await?;
+
+        Ok(())
+    }
+}
+
+#[interface(name = "org.mpris.MediaPlayer2.Player")]
+impl Player {
+    async fn play_pause(&self) -> fdo::Result<()> {
+        self.request(IpcRequest::Toggle).await.map(drop)
+    }
+
+    async fn play(&self) -> fdo::Result<()> {
+        self.request(IpcRequest::Play).await.map(drop)
+    }
+
+    async fn pause(&self) -> fdo::Result<()> {
+        self.request(IpcRequest::Pause).await.map(drop)
+    }
+
+    async fn stop(&self) -> fdo::Result<()> {
+        self.request(IpcRequest::Stop).await.map(drop)
+    }
+
+    async fn next(&self) -> fdo::Result<()> {
+        self.request(IpcRequest::Next).await.map(drop)
+    }
+
+    async fn previous(&self) -> fdo::Result<()> {
+        self.request(IpcRequest::Previous).await.map(drop)
+    }
+
+    async fn seek(
+        &self,
+        offset: i64,
+        #[zbus(signal_emitter)] emitter: SignalEmitter<'_>,
+    ) -> fdo::Result<()> {
+        self.seek_by(offset, &emitter).await
+    }
+
+    async fn set_position(
+        &self,
+        track_id: ObjectPath<'_>,
+        position: i64,
+        #[zbus(signal_emitter)] emitter: SignalEmitter<'_>,
+    ) -> fdo::Result<()> {
+        let status = self.status().await?;
+
+        // 그 사이에 곡이 바뀌었으면 무시 (MPRIS 명세)
+        if status.index.map(track_id_of).as_deref() != Some(track_id.as_str()) {
+            return Ok(());
+        }
+
+        let offset = position - status.position_millis as i64 * 1000;
+        self.seek_by(offset, &emitter).await
+    }
+
+    async fn open_uri(&self, uri: &str) -> fdo::Result<()> {
+        let path = file_uri_to_path(uri)
+            .ok_or_else(|| fdo::Error::InvalidArgs(format!("Unsupported URI: {uri}")))?;
+
+        self.request(IpcRequest::Open {
+            paths: vec![path],
+            enqueue: false,
+        })
+        .await
+        .map(drop)
+    }
+
+    #[zbus(signal)]
+    async fn seeked(emitter: &SignalEmitter<'_>, position: i64) -> zbus::Result<()>;
+
+    #[zbus(property)]
+    async fn playback_status(&self) -> fdo::Result<String> {
+        let status = match self.status().await?.state {
+            PlaybackState::Playing => "Playing",
+            PlaybackState::Paused => "Paused",
+            PlaybackState::Stopped => "Stopped",
+        };
+
+        Ok(status.into())
+    }
+
+    /// musica는 항상 목록 끝에서 처음으로 돌아간다.
+    #[zbus(property)]
+    fn loop_status(&self) -> &str {
+        "Playlist"
+    }
+
+    #[zbus(property)]
+    fn set_loop_status(&self, _loop_status: &str) -> zbus::Result<()> {
+        Err(fdo::Error::NotSupported("Loop status can't be changed".into()).into())
+    }
+
+    #[zbus(property)]
+    fn rate(&self) -> f64 {
+        1.0
+    }
+
+    #[zbus(property)]
+    fn minimum_rate(&self) -> f64 {
+        1.0
+    }
+
+    #[zbus(property)]
+    fn maximum_rate(&self) -> f64 {
+        1.0
+    }
+
+    #[zbus(property)]
+    async fn shuffle(&self) -> fdo::Result<bool> {
+        Ok(self.status().await?.shuffle)
+    }
+
+    #[zbus(property)]
+    async fn set_shuffle(&mut self, shuffle: bool) -> zbus::Result<()> {
+        self.request(IpcRequest::Shuffle { shuffle }).await?;
+
+        Ok(())
+    }
+
+    #[zbus(property)]
+    async fn metadata(&self) -> fdo::Result<HashMap<String, OwnedValue>> {
+        Ok(metadata(&self.status().await?))
+    }
+
+    #[zbus(property)]
+    async fn volume(&self) -> fdo::Result<f64> {
+        let status = self.status().await?;
+
+        Ok(if status.is_muted {
+            0.0
+        } else {
+            status.volume as f64
+        })
+    }
+
+    #[zbus(property)]
+    async fn set_volume(&mut self, volume: f64) -> zbus::Result<()> {
+        self.request(IpcRequest::Volume {
+            volume: volume.clamp(0.0, 1.0) as f32,
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// 위치는 계속 바뀌므로 변경 신호를 보내지 않는다. (MPRIS 명세)
+    #[zbus(property(emits_changed_signal = "false"))]
+    async fn position(&self) -> fdo::Result<i64> {
+        Ok(self.status().await?.position_millis as i64 * 1000)
+    }
+
+    #[zbus(property)]
+    fn can_go_next(&self) -> bool {
+        true
+    }
+
+    #[zbus(property)]
+    fn can_go_previous(&self) -> bool {
+        true
+    }
+
+    #[zbus(property)]
+    fn can_play(&self) -> bool {
+        true
+    }
+
+    #[zbus(property)]
+    fn can_pause(&self) -> bool {
+        true
+    }
+
+    #[zbus(property)]
+    fn can_seek(&self) -> bool {
+        true
+    }
+
+    #[zbus(property(emits_changed_signal = "const"))]
+    fn can_control(&self) -> bool {
+        true
+    }
+}
+
+/// MPRIS Metadata (xesam 필드)
+fn metadata(status: &PlayerStatus) -> HashMap<String, OwnedValue> {
+    let mut metadata = HashMap::new();
+
+    let mut insert = |key: &str, value: Value<'_>| {
+        if let Ok(value) = value.try_to_owned() {
+            metadata.insert(key.to_string(), value);
+        }
+    };
+
+    let Some(index) = status.index else {
+        // 재생할 곡이 없으면 NoTrack
+        if let Ok(track_id) = ObjectPath::try_from("/org/mpris/MediaPlayer2/TrackList/NoTrack") {
+            insert("mpris:trackid", track_id.into());
+        }
+
+        return metadata;
+    };
+
+    if let Ok(track_id) = ObjectPath::try_from(track_id_of(index)) {
+        insert("mpris:trackid", track_id.into());
+    }
+
+    if let Some(duration_millis) = status.duration_millis {
+        insert("mpris:length", (duration_millis as i64 * 1000).into());
+    }
+
+    if let Some(title) = &status.title {
+        insert("xesam:title", title.as_str().into());
+    }
+
+    if let Some(artist) = &status.artist {
+        insert("xesam:artist", vec![artist.as_str()].into());
+    }
+
+    if let Some(album) = &status.album {
+        insert("xesam:album", album.as_str().into());
+    }
+
+    if let Some(file_path) = &status.file_path {
+        insert("xesam:url", path_to_file_uri(file_path).into());
+    }
+
+    metadata
+}
+
+fn track_id_of(index: usize) -> String {
+    format!("{TRACK_ID_PREFIX}{index}")
+}
+
+/// 경로를 file:// URI로 바꾼다. (URI에 쓸 수 없는 바이트는 %XX로)
+fn path_to_file_uri(path: &std::path::Path) -> String {
+    let mut uri = String::from("file://");
+
+    for &byte in path.to_string_lossy().as_bytes() {
+        if byte.is_ascii_alphanumeric() || b"/-_.~".contains(&byte) {
+            uri.push(byte as char);
+        } else {
+            uri.push_str(&format!("%{byte:02X}"));
+        }
+    }
+
+    uri
+}
+
+/// file:// URI를 경로로 바꾼다. (%XX는 원래 바이트로)
+fn file_uri_to_path(uri: &str) -> Option<PathBuf> {
+    let encoded = uri.strip_prefix("file://")?;
+    let encoded = encoded.as_bytes();
+
+    let mut bytes = Vec::with_capacity(encoded.len());
+    let mut i = 0;
+
+    while i < encoded.len() {
+        if encoded[i] == b'%'
+            && let Some(hex) = encoded.get(i + 1..i + 3)
+            && let Ok(byte) = u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16)
+        {
+            bytes.push(byte);
+            i += 3;
+        } else {
+            bytes.push(encoded[i]);
+            i += 1;
+        }
+    }
+
+    Some(PathBuf::from(String::from_utf8(bytes).ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process::{Child, Command, Stdio};
+
+    use super::*;
+    use crate::ipc;
+
+    /// 테스트용 개인 세션 버스 (테스트가 끝나면 종료)
+    struct PrivateBus {
+        process: Child,
+        address: String,
+    }
+
+    impl PrivateBus {
+        fn start() -> Option<Self> {
+            use std::io::{BufRead, BufReader};
+
+            let mut process = Command::new("dbus-daemon")
+                .args(["--session", "--print-address", "--nofork"])
+                .stdout(Stdio::piped())
+                .stderr(Stdio::null())
+                .spawn()
+                .ok()?;
+
+            let mut address = String::new();
+            BufReader::new(process.stdout.take()?)
+                .read_line(&mut address)
+                .ok()?;
+
+            Some(Self {
+                process,
+                address: address.trim().to_string(),
+            })
+        }
+    }
+
+    impl Drop for PrivateBus {
+        fn drop(&mut self) {
+            let _ = self.process.kill();
+            let _ = self.process.wait();
+        }
+    }
+
+    #[zbus::proxy(
+        interface = "org.mpris.MediaPlayer2.Player",
+        default_service = "org.mpris.MediaPlayer2.musica",
+        default_path = "/org/mpris/MediaPlayer2"
+    )]
+    trait Player {
+        fn play_pause(&self) -> zbus::Result<()>;
+        fn seek(&self, offset: i64) -> zbus::Result<()>;
+
+        #[zbus(property)]
+        fn playback_status(&self) -> zbus::Result<String>;
+        #[zbus(property)]
+        fn metadata(&self) -> zbus::Result<HashMap<String, OwnedValue>>;
+        #[zbus(property)]
+        fn volume(&self) -> zbus::Result<f64>;
+        #[zbus(property)]
+        fn set_volume(&self, volume: f64) -> zbus::Result<()>;
+    }
+
+    #[test]
+    #[ignore = "needs dbus-daemon (cargo test -- --ignored)"]
+    fn controls_player_over_private_session_bus() {
+        let bus = PrivateBus::start().expect("dbus-daemon is not available");
+
+        // 앱 대신 요청을 받아 기록하고 고정된 상태로 응답
+        let (ipc_sender, requests) = ipc::spawn_fake_frontend(
+            PlayerStatus {
+                state: PlaybackState::Playing,
+                index: Some(2),
+                title: Some("Title".into()),
+                artist: Some("Artist".into()),
+                album: None,
+                file_path: Some(PathBuf::from("/music/a b.mp3")),
+                position_millis: 10_000,
+                duration_millis: Some(60_000),
+                volume: 0.5,
+                is_muted: false,
+                shuffle: false,
+                stop_after_current: false,
+                queue_length: 3,
+            },
+            Vec::new(),
+        );
+        let background_state = BackgroundState::new(false);
+        serve(
+            Some(bus.address.clone()),
+            ipc_sender,
+            background_state.clone(),
+        );
+
+        let connection = zbus::blocking::connection::Builder::address(bus.address.as_str())
+            .unwrap()
+            .build()
+            .unwrap();
+
+        // 서버가 이름을 얻을 때까지 기다린다.
+        let proxy = (0..50)
+            .find_map(|_| {
+                let proxy = PlayerProxyBlocking::new(&connection).ok()?;
+                if proxy.playback_status().is_ok() {
+                    Some(proxy)
+                } else {
+                    std::thread::sleep(std::time::Duration::from_millis(100));
+                    None
+                }
+            })
+            .expect("MPRIS server did not start");
+
+        assert_eq!("Playing", proxy.playback_status().unwrap());
+        assert_eq!(0.5, proxy.volume().unwrap());
+
+        let metadata = proxy.metadata().unwrap();
+        assert_eq!(
+            Value::from("Title"),
+            Value::from(metadata["xesam:title"].clone())
+        );
+        assert_eq!(
+            Value::from("file:///music/a%20b.mp3"),
+            Value::from(metadata["xesam:url"].clone())
+        );
+        assert_eq!(
+            Value::from(60_000_000_i64),
+            Value::from(metadata["mpris:length"].clone())
+        );
+
+        // 태그를 읽으면 Metadata가 바뀌었다고 알린다.
+        let properties = zbus::blocking::fdo::PropertiesProxy::builder(&connection)
+            .destination(BUS_NAME)
+            .unwrap()
+            .path(OBJECT_PATH)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut property_changes = properties.receive_properties_changed().unwrap();
+        background_state.publish(BackgroundStatusEvent::MetadataLoaded);
+        let change = property_changes.next().unwrap();
+        assert!(
+            change
+                .args()
+                .unwrap()
+                .changed_properties()
+                .contains_key("Metadata")
+        );
+
+        proxy.play_pause().unwrap();
+        proxy.seek(-5_000_000).unwrap();
+        proxy.set_volume(0.25).unwrap();
+
+        assert_eq!(
+            vec![
+                IpcRequest::Toggle,
+                IpcRequest::Seek {
+                    offset_millis: -5_000
+                },
+                IpcRequest::Volume { volume: 0.25 },
+            ],
+            *requests.lock().unwrap()
+        );
+    }
+
+    #[test]
+    fn converts_file_uris() {
+        let path = PathBuf::from("/music/한글 #1.mp3");
+        let uri = path_to_file_uri(&path);
+
+        assert!(uri.starts_with("file:///music/%ED"));
+        assert_eq!(Some(path), file_uri_to_path(&uri));
+        assert_eq!(None, file_uri_to_path("http://example.com/a.mp3"));
+    }
+}
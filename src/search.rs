@@ -0,0 +1,99 @@
+use musica_core::state::Music;
+use unicode_normalization::UnicodeNormalization;
+use unicode_normalization::char::is_combining_mark;
+
+/// 비교용 문자열로 바꾼다.
+/// 호환 분해(NFKD) 후 결합 문자(악센트 등)를 지우고 소문자로 맞춘다.
+/// 한글 음절은 자모로 분해되므로 입력 중인 글자("하")로도 완성된 글자("한")를 찾을 수 있다.
+pub fn normalize(text: &str) -> String {
+    text.nfkd()
+        .filter(|&c| !is_combining_mark(c))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// 재생 목록 검색어
+#[derive(Debug, Clone, Default)]
+pub struct SearchQuery {
+    normalized: String,
+}
+
+impl SearchQuery {
+    pub fn new(query: &str) -> Self {
+        Self {
+            normalized: normalize(query.trim()),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.normalized.is_empty()
+    }
+
+    /// 제목, 아티스트, 앨범, 파일 경로 중 하나라도 검색어를 포함하면 true
+    pub fn matches(&self, music: &Music) -> bool {
+        if self.is_empty() {
+            return true;
+        }
+
+        let metadata = &music.metadata;
+
+        [
+            Some(music.title.as_str()),
+            metadata.title.as_deref(),
+            metadata.artist.as_deref(),
+            metadata.album.as_deref(),
+            music.file_path.to_str(),
+        ]
+        .into_iter()
+        .flatten()
+        .any(|field| normalize(field).contains(&self.normalized))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use musica_core::metadata::MusicMetadata;
+
+    fn music(title: &str, artist: &str) -> Music {
+        Music {
+            title: title.into(),
+            file_path: PathBuf::from(format!("/music/{title}")),
+            metadata: MusicMetadata {
+                artist: Some(artist.into()),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn ignores_case_and_diacritics() {
+        let music = music("Café Del Mar.mp3", "Beyoncé");
+
+        assert!(SearchQuery::new("cafe del").matches(&music));
+        assert!(SearchQuery::new("BEYONCE").matches(&music));
+        assert!(SearchQuery::new("  ").matches(&music));
+        assert!(!SearchQuery::new("cafe mar").matches(&music));
+    }
+
+    #[test]
+    fn matches_hangul_while_typing() {
+        let music = music("한국 노래.mp3", "아이유");
+
+        assert!(SearchQuery::new("한국").matches(&music));
+        // 조합 중인 글자와 호환 자모도 찾을 수 있어야 한다.
+        assert!(SearchQuery::new("하").matches(&music));
+        assert!(SearchQuery::new("ㅇ").matches(&music));
+        assert!(!SearchQuery::new("노래방").matches(&music));
+    }
+
+    #[test]
+    fn matches_file_path() {
+        let music = music("a.mp3", "someone");
+
+        assert!(SearchQuery::new("/music/").matches(&music));
+    }
+}
@@ -0,0 +1,477 @@
+use std::sync::mpsc;
+
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use musica_core::player::Player;
+use musica_core::shortcut::{KeyPress, SEEK_STEP_MILLIS, ShortcutAction, VOLUME_STEP};
+use musica_core::state::format_duration;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Clear, LineGauge, List, ListItem, ListState, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+
+use crate::cli::Cli;
+use crate::control::{self, PlayerEvent};
+use crate::ipc::{IpcReceiver, IpcSender};
+
+/// PageUp/PageDown으로 한 번에 움직이는 줄 수
+const PAGE_STEP: u16 = 10;
+
+/// 강조 색상 (재생 중인 곡, 진행 막대)
+const ACCENT_COLOR: Color = Color::Cyan;
+
+/// 단축키 설정과 상관없이 TUI에서만 쓰는 키
+const TUI_KEYS: [(&str, &str); 5] = [
+    ("J / K", "Move selection"),
+    ("Enter", "Play selected"),
+    ("C", "Select current"),
+    ("Esc", "Close this help"),
+    ("Q", "Quit"),
+];
+
+/// TUI가 기다리는 입력
+enum TuiEvent {
+    Player(PlayerEvent),
+    Key(KeyEvent),
+    Resized,
+}
+
+impl From<PlayerEvent> for TuiEvent {
+    fn from(event: PlayerEvent) -> Self {
+        TuiEvent::Player(event)
+    }
+}
+
+/// 터미널에서 실행한다. (SSH 등 창을 띄울 수 없는 곳에서 사용)
+/// 화면과 같은 Player로 재생하므로 ctl, MPRIS, HTTP API, MPD로도 계속 제어할 수 있다.
+pub fn run(cli: &Cli, ipc_sender: IpcSender, ipc_receiver: IpcReceiver) -> anyhow::Result<()> {
+    let player = control::start_player(cli, ipc_sender);
+
+    let (event_sender, event_receiver) = mpsc::channel();
+    control::forward_events(&player, &ipc_receiver, event_sender.clone());
+
+    std::thread::spawn(move || {
+        loop {
+            let event = match crossterm::event::read() {
+                Ok(Event::Key(key)) => TuiEvent::Key(key),
+                Ok(Event::Resize(..)) => TuiEvent::Resized,
+                Ok(_) => continue,
+                Err(_) => return,
+            };
+
+            if event_sender.send(event).is_err() {
+                return;
+            }
+        }
+    });
+
+    let mut app = TuiApp {
+        player,
+        list_state: ListState::default(),
+        show_shortcuts: false,
+        should_quit: false,
+    };
+
+    let mut terminal = ratatui::try_init()?;
+    let result = app.run(&mut terminal, event_receiver);
+    ratatui::restore();
+
+    app.player.shutdown();
+
+    result
+}
+
+struct TuiApp {
+    player: Player,
+    list_state: ListState, // 재생 목록에서 선택한 줄
+    show_shortcuts: bool,
+    should_quit: bool,
+}
+
+impl TuiApp {
+    fn run(
+        &mut self,
+        terminal: &mut DefaultTerminal,
+        event_receiver: mpsc::Receiver<TuiEvent>,
+    ) -> anyhow::Result<()> {
+        while !self.should_quit {
+            terminal.draw(|frame| self.view(frame))?;
+
+            let Ok(event) = event_receiver.recv() else {
+                break;
+            };
+            self.update(event);
+
+            // 재생 위치 등 밀린 이벤트는 한꺼번에 처리하고 한 번만 다시 그린다.
+            while !self.should_quit
+                && let Ok(event) = event_receiver.try_recv()
+            {
+                self.update(event);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn update(&mut self, event: TuiEvent) {
+        match event {
+            TuiEvent::Player(event) => {
+                if control::handle_event(&mut self.player, event) {
+                    self.should_quit = true;
+                }
+
+                // 처음에는 재생 중인 곡을 선택
+                if self.list_state.selected().is_none()
+                    && self.player.main_state.music_list.is_not_empty()
+                {
+                    self.select_current_music();
+                }
+            }
+            TuiEvent::Key(key) if key.kind == KeyEventKind::Press => self.handle_key(key),
+            TuiEvent::Key(_) | TuiEvent::Resized => {}
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        let is_ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+
+        match key.code {
+            KeyCode::Char('c') if is_ctrl => self.should_quit = true,
+            KeyCode::Char('q') => self.should_quit = true,
+            KeyCode::Esc if self.show_shortcuts => self.show_shortcuts = false,
+            KeyCode::Char('j') => self.list_state.select_next(),
+            KeyCode::Char('k') => self.list_state.select_previous(),
+            KeyCode::PageDown => self.list_state.scroll_down_by(PAGE_STEP),
+            KeyCode::PageUp => self.list_state.scroll_up_by(PAGE_STEP),
+            KeyCode::Home => self.list_state.select_first(),
+            KeyCode::End => self.list_state.select_last(),
+            KeyCode::Char('c') => self.select_current_music(),
+            KeyCode::Enter => {
+                let music_count = self.player.main_state.music_list.list.len();
+
+                if let Some(index) = self.list_state.selected()
+                    && music_count > 0
+                {
+                    self.player.play_index(index.min(music_count - 1));
+                }
+            }
+            _ => {
+                if let Some(key_press) = key_press(key)
+                    && let Some(action) =
+                        self.player.config_data.key_bindings.action_for(&key_press)
+                {
+                    self.run_shortcut(action);
+                }
+            }
+        }
+    }
+
+    fn run_shortcut(&mut self, action: ShortcutAction) {
+        let volume = self.player.config_data.volume;
+
+        match action {
+            ShortcutAction::PlayPause => self.player.toggle_pause(),
+            ShortcutAction::Next => self.player.next(),
+            ShortcutAction::Previous => self.player.previous(),
+            ShortcutAction::SeekForward => self.player.seek_by(SEEK_STEP_MILLIS),
+            ShortcutAction::SeekBackward => self.player.seek_by(-SEEK_STEP_MILLIS),
+            ShortcutAction::VolumeUp => self.player.set_volume(volume + VOLUME_STEP),
+            ShortcutAction::VolumeDown => self.player.set_volume(volume - VOLUME_STEP),
+            ShortcutAction::Mute => self.player.toggle_mute(),
+            ShortcutAction::ToggleShuffle => {
+                self.player
+                    .set_random_mode(!self.player.config_data.is_random);
+            }
+            // TUI에는 설정 화면이 없다.
+            ShortcutAction::OpenSettings => {}
+            ShortcutAction::ShowShortcuts => self.show_shortcuts = !self.show_shortcuts,
+        }
+    }
+
+    fn select_current_music(&mut self) {
+        self.list_state
+            .select(Some(self.player.main_state.current_music_index));
+    }
+
+    fn view(&mut self, frame: &mut Frame) {
+        let [now_playing_area, list_area, help_area] = Layout::vertical([
+            Constraint::Length(6),
+            Constraint::Min(3),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        self.now_playing_view(frame, now_playing_area);
+        self.list_view(frame, list_area);
+
+        let help = Line::from(vec![
+            Span::raw(format!(
+                " {} ",
+                self.player.config_data.key_bindings.play_pause
+            )),
+            Span::raw("play/pause  ").dim(),
+            Span::raw("Enter "),
+            Span::raw("play selected  ").dim(),
+            Span::raw(format!(
+                "{} ",
+                self.player.config_data.key_bindings.show_shortcuts
+            )),
+            Span::raw("shortcuts  ").dim(),
+            Span::raw("Q "),
+            Span::raw("quit").dim(),
+        ]);
+        frame.render_widget(Paragraph::new(help), help_area);
+
+        if self.show_shortcuts {
+            self.shortcuts_view(frame);
+        }
+    }
+
+    fn now_playing_view(&self, frame: &mut Frame, area: Rect) {
+        let main_state = &self.player.main_state;
+        let music = main_state
+            .music_list
+            .list
+            .get(main_state.current_music_index);
+
+        let state_symbol = if main_state.is_stopped {
+            "■"
+        } else if main_state.on_play {
+            "▶"
+        } else {
+            "⏸"
+        };
+
+        let title = music
+            .map(|music| {
+                music
+                    .metadata
+                    .title
+                    .clone()
+                    .unwrap_or_else(|| music.title.clone())
+            })
+            .unwrap_or_else(|| main_state.title.clone());
+
+        let artist_album = music
+            .map(|music| {
+                [&music.metadata.artist, &music.metadata.album]
+                    .into_iter()
+                    .flatten()
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join(" — ")
+            })
+            .unwrap_or_default();
+
+        let mut status = vec![if main_state.is_muted {
+            "muted".to_string()
+        } else {
+            format!("vol {:.0}%", self.player.config_data.volume * 100.0)
+        }];
+
+        if self.player.config_data.is_random {
+            status.push("shuffle".into());
+        }
+
+        if main_state.stop_after_current {
+            status.push("stop after current".into());
+        }
+
+        if !main_state.is_output_available {
+            status.push("no output device".into());
+        }
+
+        let block = Block::bordered().title(" musica ");
+        let inner_area = block.inner(area);
+        frame.render_widget(block, area);
+
+        let [title_area, artist_area, progress_area, status_area] =
+            Layout::vertical([Constraint::Length(1); 4]).areas(inner_area);
+
+        frame.render_widget(
+            Paragraph::new(Line::from(vec![
+                Span::raw(format!("{state_symbol} ")).fg(ACCENT_COLOR),
+                Span::raw(title).bold(),
+            ])),
+            title_area,
+        );
+        frame.render_widget(Paragraph::new(artist_album).dim(), artist_area);
+
+        let ratio = match main_state.duration {
+            Some(duration) if !duration.is_zero() => {
+                (main_state.position.as_secs_f64() / duration.as_secs_f64()).clamp(0.0, 1.0)
+            }
+            _ => 0.0,
+        };
+
+        let time = match main_state.duration {
+            Some(duration) => format!(
+                "{} / {}",
+                format_duration(main_state.position),
+                format_duration(duration)
+            ),
+            None => format_duration(main_state.position),
+        };
+
+        frame.render_widget(
+            LineGauge::default()
+                .ratio(ratio)
+                .label(time)
+                .filled_style(Style::default().fg(ACCENT_COLOR)),
+            progress_area,
+        );
+        frame.render_widget(Paragraph::new(status.join("  ")).dim(), status_area);
+    }
+
+    fn list_view(&mut self, frame: &mut Frame, area: Rect) {
+        let main_state = &self.player.main_state;
+
+        let items = main_state
+            .music_list
+            .list
+            .iter()
+            .enumerate()
+            .map(|(index, music)| {
+                let is_current_music = index == main_state.current_music_index;
+
+                let mut spans = vec![
+                    Span::raw(if is_current_music { "♪ " } else { "  " }),
+                    Span::raw(
+                        music
+                            .metadata
+                            .title
+                            .clone()
+                            .unwrap_or_else(|| music.title.clone()),
+                    ),
+                ];
+
+                if let Some(artist) = &music.metadata.artist {
+                    spans.push(Span::raw(format!("  {artist}")).dim());
+                }
+
+                if let Some(duration) = music.metadata.duration {
+                    spans.push(Span::raw(format!("  {}", format_duration(duration))).dim());
+                }
+
+                let style = if music.error.is_some() {
+                    Style::default().fg(Color::Red)
+                } else if is_current_music {
+                    Style::default().fg(ACCENT_COLOR).bold()
+                } else {
+                    Style::default()
+                };
+
+                ListItem::new(Line::from(spans)).style(style)
+            })
+            .collect::<Vec<_>>();
+
+        let list = List::new(items)
+            .block(
+                Block::bordered().title(format!(" Queue ({}) ", main_state.music_list.list.len())),
+            )
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+        frame.render_stateful_widget(list, area, &mut self.list_state);
+    }
+
+    fn shortcuts_view(&self, frame: &mut Frame) {
+        let key_bindings = &self.player.config_data.key_bindings;
+
+        let lines = ShortcutAction::ALL
+            .into_iter()
+            .filter(|action| *action != ShortcutAction::OpenSettings)
+            .map(|action| (key_bindings.binding(action), action.description()))
+            .chain(TUI_KEYS)
+            .map(|(binding, description)| {
+                Line::from(vec![
+                    Span::raw(format!("{binding:>12}  ")).bold(),
+                    Span::raw(description),
+                ])
+            })
+            .collect::<Vec<_>>();
+
+        let area = frame.area();
+        let width = 40.min(area.width);
+        let height = (lines.len() as u16 + 2).min(area.height);
+        let area = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        );
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::bordered().title(" Shortcuts ")),
+            area,
+        );
+    }
+}
+
+/// crossterm 키 입력을 단축키 비교용 형태로 바꾼다. (iced 화면과 같은 키 이름 사용)
+fn key_press(key: KeyEvent) -> Option<KeyPress> {
+    let name = match key.code {
+        KeyCode::Char(' ') => "space".to_string(),
+        KeyCode::Char(c) => c.to_lowercase().to_string(),
+        KeyCode::Left => "arrowleft".into(),
+        KeyCode::Right => "arrowright".into(),
+        KeyCode::Up => "arrowup".into(),
+        KeyCode::Down => "arrowdown".into(),
+        KeyCode::Enter => "enter".into(),
+        KeyCode::Esc => "escape".into(),
+        KeyCode::Tab => "tab".into(),
+        KeyCode::Backspace => "backspace".into(),
+        KeyCode::Delete => "delete".into(),
+        KeyCode::Insert => "insert".into(),
+        KeyCode::Home => "home".into(),
+        KeyCode::End => "end".into(),
+        KeyCode::PageUp => "pageup".into(),
+        KeyCode::PageDown => "pagedown".into(),
+        KeyCode::F(number) => format!("f{number}"),
+        _ => return None,
+    };
+
+    Some(KeyPress {
+        key: name,
+        ctrl: key.modifiers.contains(KeyModifiers::CONTROL),
+        shift: key.modifiers.contains(KeyModifiers::SHIFT),
+        alt: key.modifiers.contains(KeyModifiers::ALT),
+        logo: key
+            .modifiers
+            .intersects(KeyModifiers::SUPER | KeyModifiers::META),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use musica_core::shortcut::KeyBindings;
+
+    #[test]
+    fn maps_terminal_keys_to_default_bindings() {
+        let key_bindings = KeyBindings::default();
+        let action = |code, modifiers| {
+            key_press(KeyEvent::new(code, modifiers))
+                .and_then(|key_press| key_bindings.action_for(&key_press))
+        };
+
+        assert_eq!(
+            Some(ShortcutAction::PlayPause),
+            action(KeyCode::Char(' '), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            Some(ShortcutAction::SeekBackward),
+            action(KeyCode::Left, KeyModifiers::NONE)
+        );
+        assert_eq!(
+            Some(ShortcutAction::OpenSettings),
+            action(KeyCode::Char(','), KeyModifiers::CONTROL)
+        );
+        // Shift를 눌러 입력한 '?'도 그대로 찾는다.
+        assert_eq!(
+            Some(ShortcutAction::ShowShortcuts),
+            action(KeyCode::Char('?'), KeyModifiers::SHIFT)
+        );
+        assert_eq!(None, action(KeyCode::Char('N'), KeyModifiers::SHIFT));
+    }
+}